@@ -6,7 +6,6 @@ use web_sys::wasm_bindgen::JsCast;
 pub fn main() {
     let _ = console_log::init_with_level(log::Level::Debug);
     console_error_panic_hook::set_once();
-    Swal::init_key_handlers();
     mount_to_body(|| view! { <App /> });
 }
 
@@ -90,24 +89,33 @@ fn App() -> impl IntoView {
     };
 
     let question = move |_| {
-        Swal::fire(SwalOptions {
+        // The value of this alert is the value of its input.
+        Swal::fire(SwalOptions::<&str, SwalIcon, String> {
             title: "This is a question",
             text: "What is your name ?",
             icon: SwalIcon::QUESTION,
             show_confirm_button: true,
-            body: view! { <input id="name-input" style="margin-bottom: 30px" /> }.into_view(),
-            then: |_| {
-                let input = document()
-                    .get_element_by_id("name-input")
+            // The body is created when the popup opens.
+            body: SwalBody::from(|| view! { <input id="name-input" style="margin-bottom: 30px" /> }),
+            did_open: |popup| {
+                // The input is focused once the popup is opened.
+                if let Some(input) = popup
+                    .query_selector("#name-input")
                     .unwrap()
-                    .dyn_into::<web_sys::HtmlInputElement>()
-                    .unwrap();
-                Swal::fire(SwalOptions::<String> {
-                    title: format!("Your name is {}", input.value()),
-                    ..SwalOptions::default()
-                });
+                    .and_then(|input| input.dyn_into::<web_sys::HtmlElement>().ok())
+                {
+                    let _ = input.focus();
+                }
             },
-            ..SwalOptions::default()
+            then: |result: SwalResult<String>| {
+                if let Some(name) = result.into_confirmed() {
+                    Swal::fire(SwalOptions::<String> {
+                        title: format!("Your name is {}", name),
+                        ..SwalOptions::default()
+                    });
+                }
+            },
+            ..SwalOptions::typed()
         });
     };
 
@@ -1,157 +1,737 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::ops::Deref;
 use std::time::Duration;
 
-use crate::{SwalDismissReason, SwalIconLike, SwalResult};
+use crate::focus::{active_element_of, find_focusables, focus_first, unwatch_focus, watch_focus};
+use crate::inert::{make_siblings_inert, restore_siblings};
+use crate::registry::{self, SwalHook, SwalHooks, SwalInstance};
+use crate::scroll_lock::{lock_scroll, unlock_scroll};
+use crate::swal_error::{log_error, try_body, try_document};
+use crate::swal_isolation::mount_point;
+use crate::swal_options::css_override;
+use crate::swal_result::AnyResult;
+use crate::transition::when_animations_end;
+use crate::{
+    SwalAction, SwalBackdrop, SwalContext, SwalDismissReason, SwalError, SwalHandle, SwalIconLike,
+    SwalFuture, SwalResult, SwalState, SwalValue,
+};
 
 use crate::SwalOptions;
 use leptos::html::{AnyElement, Div};
-use leptos::{set_timeout, *};
+use leptos::*;
+use leptos_dom::helpers::WindowListenerHandle;
 use leptos_dom::HtmlElement;
 
 use web_sys::wasm_bindgen::JsCast;
-use web_sys::{window, Element, HtmlCollection, MouseEvent};
+use web_sys::{Element, KeyboardEvent, MouseEvent};
 
-thread_local! {
-    /// The duration of the transition that opens and closes the swal.
-    /// It is stored this way to avoid re-doing the calculations
-    /// every time that a swal is being opened or closed.
-    static TRANSITION_DURATION: RefCell<f32> = const { RefCell::new(-1.0) };
+pub(crate) use crate::registry::Resolver;
+
+/// The time between adding an alert to the DOM and showing it,
+/// so that the browser renders it hidden first and its opening
+/// animation can start.
+const OPENING_DELAY: Duration = Duration::from_millis(10);
 
-    /// This is a copy of the "then" callback that was given to the current alert.
-    /// The point of this variable is to be able to execute the callback when the alert
-    /// gets closed by the Escape key or by clicking on the backdrop.
-    static THEN_CALLBACK: RefCell<Option<fn(SwalResult)>> = const { RefCell::new(None) };
+thread_local! {
+    /// The window listener handling the keys pressed outside of the alerts
+    /// (see [`on_window_keydown`]). It's added when an alert opens
+    /// and removed once there is no alert anymore.
+    static KEY_LISTENER: RefCell<KeyListener<WindowListenerHandle, KeyboardEvent>> =
+        const { RefCell::new(KeyListener::new()) };
 
-    /// The "auto_close" parameter of the current options.
-    static AUTO_CLOSE: RefCell<bool> = const { RefCell::new(true) };
+    /// The alert whose "then" callback is running, if any (see [`replaced_parent`]).
+    static RESOLVING: RefCell<Option<String>> = const { RefCell::new(None) };
 
-    /// The element that had the focus before opening the Swal.
-    static PREVIOUSLY_FOCUSED: RefCell<Option<web_sys::HtmlElement>> = const { RefCell::new(None) };
+    /// The reactive owner of the code run by the browser (the event listeners,
+    /// the timeouts, etc.), which is the root of the reactive runtime.
+    /// It's known once an alert was opened (see [`create_root`]).
+    static ROOT_OWNER: Cell<Option<Owner>> = const { Cell::new(None) };
 }
 
 /// Creates a Sweet Alert with the options defined in `opt`.
 /// See the docs for [`SwalOptions`] to know how to use it.
-pub fn fire<S, I>(opt: SwalOptions<S, I>)
+///
+/// It returns a [`SwalHandle`] to control this specific alert.
+/// You don't have to use it.
+///
+/// If the alert cannot be opened, the error is logged
+/// and the returned handle is already closed (see [`try_fire`]).
+pub fn fire<S, I, T>(opt: SwalOptions<S, I, T>) -> SwalHandle<T>
 where
     S: AsRef<str> + Clone + Default + leptos::IntoView + 'static,
     I: SwalIconLike + Default + Clone + Copy + 'static,
+    T: SwalValue,
 {
-    if let Some(swal) = get_swal() {
+    handle_or_log(try_fire(opt))
+}
+
+/// Same as [`fire`], but it returns an error instead of logging it,
+/// for example if the document has no body or if one of the CSS options is invalid.
+///
+/// Note that if an alert is already opened, the new one only opens once the
+/// current one is closed. The errors occurring at that time are logged,
+/// and the handle gets closed. If the current alert was opened with [`push`],
+/// the new one takes its place above the same alert.
+///
+/// Likewise, when it's called within a reactive owner (an effect, a memo, etc.),
+/// the alert opens right after, outside of this owner, so that it isn't disposed
+/// along with it. Since this owner cannot be told apart from the code run by the
+/// browser until an alert was opened, the very first alert always opens right after.
+pub fn try_fire<S, I, T>(opt: SwalOptions<S, I, T>) -> Result<SwalHandle<T>, SwalError>
+where
+    S: AsRef<str> + Clone + Default + leptos::IntoView + 'static,
+    I: SwalIconLike + Default + Clone + Copy + 'static,
+    T: SwalValue,
+{
+    try_body()?;
+    opt.check_css()?;
+    opt.isolation.check()?;
+    let handle = SwalHandle::new();
+    let opened = handle.clone();
+    if must_wait() || must_defer() {
         // It has to be unsynced so that the current Swal can
-        // finish closing and the DOM update itself.
-        set_timeout(
-            move || {
-                open(opt);
-            },
-            Duration::from_secs_f32(0.01 + get_transition_duration(&swal)),
-        );
+        // start closing (for example when `fire` is called from
+        // `pre_confirm`) and then finish closing before the new one opens.
+        // It's canceled if the handle closes the alert in the meantime.
+        // By then, the alert that is replaced may be closing already,
+        // so the new one's place in the stack is chosen now.
+        let parent = replaced_parent();
+        let pending = set_timeout_with_handle(
+            move || replace_then(parent, move |parent| open(opt, opened, parent)),
+            Duration::ZERO,
+        )?;
+        handle.set_pending(pending);
+    } else {
+        try_open(opt, opened, None)?;
+    }
+    Ok(handle)
+}
+
+/// Checks if [`fire`] must wait before opening a new alert,
+/// because another one is opened or still closing.
+pub(crate) fn must_wait() -> bool {
+    registry::topmost().is_some() || registry::last_closing().is_some()
+}
+
+/// Checks if the alerts must open once the current code is done, because
+/// it may run within a reactive owner (an effect, a memo, etc.). The owner of
+/// an alert created there would be disposed when this owner re-runs or is
+/// disposed, while the alert is still on screen (see [`create_root`]).
+pub(crate) fn must_defer() -> bool {
+    ROOT_OWNER
+        .get()
+        .is_none_or(|root| Owner::current() != Some(root))
+}
+
+/// Gets the alert above which [`fire`] opens a new one: the parent (see [`push`])
+/// of the alert it replaces, so that an alert fired from a pushed one remains above
+/// the same alert. The replaced alert is the one whose "then" callback is running,
+/// since it's already closing, or else the topmost one.
+pub(crate) fn replaced_parent() -> Option<String> {
+    match RESOLVING.with_borrow(Option::clone) {
+        Some(id) => registry::with_instance(&id, |instance| instance.parent.clone()).flatten(),
+        None => registry::topmost_parent(),
+    }
+}
+
+/// Replaces the alerts above `parent` (see [`replaced_parent`]), then calls `open`
+/// with this parent once the alert that is closing is removed from the DOM.
+pub(crate) fn replace_then(parent: Option<String>, open: impl FnOnce(Option<String>) + 'static) {
+    // The current Swals didn't start closing, so they get
+    // replaced and their "then" callback is called right away.
+    let parent = parent.filter(|parent| registry::is_active(parent));
+    while registry::topmost().is_some() && registry::topmost() != parent {
+        replace_topmost();
+    }
+    if let Some(swal) = registry::last_closing() {
+        when_animations_end(&swal, move || open(parent));
+    } else {
+        open(parent);
+    }
+}
+
+/// Opens a Sweet Alert above the current one, instead of replacing it.
+/// The current alert remains in the DOM, dimmed and inert, and comes back
+/// (with its focus, its state and its "then" callback) when the new one closes.
+/// Until then, the buttons of the current alert don't close it.
+///
+/// If no alert is opened, it's the same as [`fire`]. Like [`fire`], it opens
+/// the alert right after when it's called within a reactive owner.
+///
+/// # Example
+///
+/// ```no_run
+/// # use leptos_sweetalert::*;
+///
+/// Swal::fire(SwalOptions::<&str> {
+///     title: "Edit the document",
+///     show_deny_button: true,
+///     deny_button_text: "Discard",
+///     pre_deny: || {
+///         Swal::push(SwalOptions::<&str> {
+///             title: "Discard the changes?",
+///             show_cancel_button: true,
+///             then: |result: SwalResult| {
+///                 if result.is_confirmed {
+///                     // The first alert is the current one again.
+///                     Swal::close(Some(SwalResult::denied()));
+///                 }
+///             },
+///             ..SwalOptions::default()
+///         });
+///     },
+///     ..SwalOptions::default()
+/// });
+/// ```
+pub fn push<S, I, T>(opt: SwalOptions<S, I, T>) -> SwalHandle<T>
+where
+    S: AsRef<str> + Clone + Default + leptos::IntoView + 'static,
+    I: SwalIconLike + Default + Clone + Copy + 'static,
+    T: SwalValue,
+{
+    handle_or_log(try_push(opt))
+}
+
+/// Same as [`push`], but it returns an error instead of logging it.
+pub fn try_push<S, I, T>(opt: SwalOptions<S, I, T>) -> Result<SwalHandle<T>, SwalError>
+where
+    S: AsRef<str> + Clone + Default + leptos::IntoView + 'static,
+    I: SwalIconLike + Default + Clone + Copy + 'static,
+    T: SwalValue,
+{
+    let Some(parent) = registry::topmost() else {
+        return try_fire(opt);
+    };
+    try_body()?;
+    opt.check_css()?;
+    opt.isolation.check()?;
+    let handle = SwalHandle::new();
+    if must_defer() {
+        let opened = handle.clone();
+        let pending = set_timeout_with_handle(
+            move || open(opt, opened, Some(parent)),
+            Duration::ZERO,
+        )?;
+        handle.set_pending(pending);
     } else {
-        open(opt);
+        try_open(opt, handle.clone(), Some(parent))?;
+    }
+    Ok(handle)
+}
+
+/// Logs the error of an alert that couldn't be opened,
+/// and returns a handle that is already closed instead.
+fn handle_or_log<T: SwalValue>(result: Result<SwalHandle<T>, SwalError>) -> SwalHandle<T> {
+    match result {
+        Ok(handle) => handle,
+        Err(error) => {
+            log_error("Could not open the Swal", &error);
+            let handle = SwalHandle::new();
+            handle.resolve(None);
+            handle
+        }
+    }
+}
+
+/// Opens the Swal, or logs the error and closes
+/// its handle if it cannot be opened.
+fn open<S, I, T>(opt: SwalOptions<S, I, T>, handle: SwalHandle<T>, parent: Option<String>)
+where
+    S: AsRef<str> + Clone + Default + leptos::IntoView + 'static,
+    I: SwalIconLike + Default + Clone + Copy + 'static,
+    T: SwalValue,
+{
+    if let Err(error) = try_open(opt, handle.clone(), parent) {
+        log_error("Could not open the Swal", &error);
+        handle.resolve(None);
     }
 }
 
 /// Creates the Swal, adds it to the DOM and sets its aria-hidden
 /// attribute to "false" so that the animation can start once the
 /// DOM was updated.
-fn open<S, I>(opt: SwalOptions<S, I>)
+///
+/// The Swal is opened above `parent`, or at the bottom of the stack if it's `None`.
+/// The alerts that are above, if any, get replaced.
+fn try_open<S, I, T>(
+    opt: SwalOptions<S, I, T>,
+    handle: SwalHandle<T>,
+    parent: Option<String>,
+) -> Result<(), SwalError>
 where
     S: AsRef<str> + Clone + Default + leptos::IntoView + 'static,
     I: SwalIconLike + Default + Clone + Copy + 'static,
+    T: SwalValue,
 {
-    document()
-        .body()
-        .expect("Could not find body")
-        .append_child(
-            &SwalComponent(opt)
-                .into_view()
-                .into_html_element()
-                .expect("Could not parse Swal to HTML"),
-        )
-        .expect("Could not append Swal to body");
-    if let Some(active_element) = get_active_element() {
-        set_previously_focused_element(active_element);
-    }
-    set_timeout(
-        || {
-            get_swal()
-                .unwrap()
-                .set_attribute("aria-hidden", "false")
-                .expect("Could not set aria-hidden of Swal");
-            let focusables = get_focusables();
-            if focusables.len() > 0 {
-                focusables[0]
-                    .focus()
-                    .expect("Could not focus first button of Swal");
+    // The alert was closed via its handle before it could be opened.
+    if handle.is_closed() {
+        return Ok(());
+    }
+    // The parent may have been closed in the meantime,
+    // and another alert may have been opened.
+    let parent = parent.filter(|parent| registry::is_active(parent));
+    while registry::topmost().is_some() && registry::topmost() != parent {
+        replace_topmost();
+    }
+    let is_modal = opt.backdrop.is_modal();
+    let is_scoped = opt.is_scoped();
+    let scrollbar_padding = opt.scrollbar_padding;
+    let height_auto = opt.height_auto;
+    let timer = opt.timer;
+    let close_on_route_change = opt.close_on_route_change;
+    let hooks = SwalHooks {
+        will_open: opt.will_open,
+        did_render: opt.did_render,
+        did_open: opt.did_open,
+        will_close: opt.will_close,
+        did_close: opt.did_close,
+        did_destroy: opt.did_destroy,
+    };
+    let mut instance = SwalInstance::new(registry::next_id(), make_resolver(handle.clone(), opt.then));
+    instance.auto_close = opt.auto_close;
+    instance.allow_enter_key = opt.allow_enter_key;
+    instance.key_bindings = opt.key_bindings.clone();
+    instance.stop_escape_propagation = opt.stop_escape_propagation;
+    instance.previously_focused = get_active_element();
+    let id = instance.id.clone();
+    let target = opt.target.resolve()?;
+    let isolation = opt.isolation.clone();
+    // The alert gets its own reactive root, in which the body is created.
+    // It's disposed once the alert is removed from the DOM.
+    let component_id = id.clone();
+    let (component, owner) = create_root(move || SwalComponent(opt.clone(), component_id.clone()));
+    instance.owner = Some(owner);
+    let swal: Element = component.deref().clone().into();
+    instance.hooks = Some(hooks);
+    instance.popup = Some(Rc::new(get_popup(&swal)));
+    run_hook(&mut instance, SwalHook::WillOpen);
+    isolation.mount(&target, &swal)?;
+    instance.root = Some(swal.clone());
+    if let Some(parent_root) = parent.as_deref().and_then(get_root) {
+        set_stacked(&parent_root, true);
+    }
+    instance.parent = parent;
+    handle.set_root(swal.clone());
+    run_hook(&mut instance, SwalHook::DidRender);
+    // The focus of a popup that leaves the rest of the page usable
+    // belongs to the user.
+    if is_modal && !is_scoped {
+        watch_focus(&swal);
+    }
+    if is_scoped {
+        // The backdrop is positioned relative to the target.
+        let is_static = web_sys::window()
+            .and_then(|window| window.get_computed_style(&target).ok().flatten())
+            .and_then(|style| style.get_property_value("position").ok())
+            .is_some_and(|position| position == "static");
+        if is_static {
+            let _ = target.class_list().add_1("swal-scoped-target");
+        }
+        instance.scoped_target = Some(target.clone());
+    }
+    if is_modal {
+        if is_scoped {
+            make_siblings_inert(&swal, &target);
+        } else {
+            make_siblings_inert(&swal, &try_body()?.into());
+            instance.locks_scroll = true;
+            if let Err(error) = lock_scroll(scrollbar_padding, height_auto) {
+                log_error("Could not lock the scroll of the page", &error);
             }
+        }
+    }
+    if close_on_route_change {
+        let (popstate_id, hashchange_id) = (id.clone(), id.clone());
+        instance.route_listeners = vec![
+            window_event_listener(ev::popstate, move |_| on_route_change(&popstate_id)),
+            window_event_listener(ev::hashchange, move |_| on_route_change(&hashchange_id)),
+        ];
+    }
+    registry::register(instance);
+    add_key_listener();
+    let opened_id = id.clone();
+    // It's canceled if the alert starts closing in the meantime.
+    let opening = set_timeout_with_handle(
+        move || {
+            let is_open = registry::with_instance(&opened_id, |instance| {
+                instance.transition(SwalState::Open)
+            });
+            if is_open != Some(true) {
+                return;
+            }
+            if let Err(error) = swal.set_attribute("aria-hidden", "false") {
+                log_error("Could not show the Swal", &error.into());
+            }
+            focus_first(&swal);
+            if let Some(timer) = timer {
+                start_timer(&opened_id, timer);
+            }
+            call_hook(&opened_id, SwalHook::DidOpen);
         },
-        Duration::from_secs_f32(0.01),
+        OPENING_DELAY,
     );
+    match opening {
+        Ok(opening) => {
+            registry::with_instance(&id, |instance| instance.opening = Some(opening));
+            Ok(())
+        }
+        Err(error) => {
+            close_id(&id, None);
+            Err(error.into())
+        }
+    }
 }
 
-/// Allows the user to close the alert by pressing the Escape key.
-/// It also holds the focus within the swal, preventing the user from
-/// focusing elements that are not inside the alert.
-///
-/// This method must be called only once, otherwise duplicated event
-/// listeners will be created and attached to the window, which is
-/// pointless and reduces performance.
-///
-/// This method must be called in the main function of your program.
+/// Runs the given function within a new reactive owner, and returns what disposes it.
+/// It's called from the code run by the browser (see [`must_defer`]), so the owner
+/// belongs to the root of the reactive runtime instead of the code that opened the
+/// alert, and only the removal of the alert disposes it.
+pub(crate) fn create_root<V>(f: impl Fn() -> V) -> (V, Disposer) {
+    ROOT_OWNER.set(Owner::current());
+    as_child_of_current_owner(move |()| f())(())
+}
+
+/// Creates the function giving the result of an alert
+/// to its "then" callback and to its handle.
+pub(crate) fn make_resolver<T: SwalValue>(
+    handle: SwalHandle<T>,
+    then: fn(SwalResult<T>),
+) -> Resolver {
+    Box::new(move |result: Option<AnyResult>| {
+        let input = handle.root().and_then(|root| get_input_of(&root));
+        let result = result.map(|result| result.downcast(|| T::from_input(input.as_ref())));
+        handle.resolve(result.clone());
+        if let Some(result) = result {
+            (then)(result);
+        }
+    })
+}
+
+/// Dismisses the topmost alert with [`SwalDismissReason::Replaced`],
+/// if there is one. It returns `false` if there was no opened alert.
+pub(crate) fn replace_topmost() -> bool {
+    registry::topmost().is_some_and(|id| dismiss_id(&id, SwalDismissReason::Replaced))
+}
+
+/// Gets the root of the opened alert with the given ID.
+fn get_root(id: &str) -> Option<Element> {
+    registry::with_instance(id, |instance| instance.root.clone()).flatten()
+}
+
+/// Dims the given alert and makes it inert while an alert opened
+/// with [`push`] is above it, or brings it back.
+fn set_stacked(swal: &Element, stacked: bool) {
+    // The host of the shadow root, if the alert is isolated.
+    let mounted = mount_point(swal);
+    if stacked {
+        let _ = swal.class_list().add_1("swal-backdrop-stacked");
+        let _ = mounted.set_attribute("inert", "");
+    } else {
+        let _ = swal.class_list().remove_1("swal-backdrop-stacked");
+        let _ = mounted.remove_attribute("inert");
+    }
+}
+
+/// Dismisses the given alert with [`SwalDismissReason::RouteChange`] when the user
+/// navigates through the history (see the "close_on_route_change" option).
+fn on_route_change(id: &str) {
+    if registry::with_instance(id, |instance| instance.auto_close).unwrap_or(false) {
+        dismiss_id(id, SwalDismissReason::RouteChange);
+    }
+}
+
+/// Dismisses the given alert with [`SwalDismissReason::Timer`]
+/// once the duration is over, unless it was closed in the meantime.
+fn start_timer(id: &str, duration: Duration) {
+    let timer_id = id.to_string();
+    let timer = set_timeout_with_handle(
+        move || {
+            dismiss_id(&timer_id, SwalDismissReason::Timer);
+        },
+        duration,
+    )
+    .ok();
+    registry::with_instance(id, |instance| {
+        if let Some(previous) = std::mem::replace(&mut instance.timer, timer) {
+            previous.clear();
+        }
+    });
+}
+
+/// Adds a window listener handling the keyboard and returns its handle,
+/// which can be used to remove it by calling `remove()` on it.
 ///
-/// It returns a handle that you can use to manually remove the event listener
-/// by calling `remove()` on the return value. You probably won't need it but it
-/// is there in case you need it.
-pub fn init_key_handlers() -> leptos_dom::helpers::WindowListenerHandle {
-    window_event_listener(ev::keydown, |ev| {
-        if is_open() {
-            let code = ev.code();
-            if code.eq("Escape") {
-                if AUTO_CLOSE.with_borrow(|a| *a) {
-                    close(Some(SwalResult::canceled(SwalDismissReason::Esc)));
-                }
-            } else if code.eq("Tab") {
-                let focusables = get_focusables();
-                if focusables.len() == 0 {
-                    return;
-                }
+/// The key handlers are now added automatically when an alert opens,
+/// and removed once the last alert is closed, so calling this function
+/// is not needed anymore. Calling it, even several times, doesn't change
+/// anything: each key is handled once, whatever the number of listeners.
+#[deprecated(note = "the key handlers are added automatically when an alert opens")]
+pub fn init_key_handlers() -> WindowListenerHandle {
+    window_event_listener(ev::keydown, on_window_keydown)
+}
 
-                let mut index: usize = 0;
-                if let Some(active_element) = document().active_element() {
-                    let active_element = active_element
-                        .dyn_ref::<web_sys::HtmlElement>()
-                        .expect("Invalid active element");
-                    for i in 0..focusables.len() {
-                        if focusables[i].is_same_node(Some(active_element)) {
-                            index = i;
-                            break;
-                        }
-                    }
-                }
+/// The window listener handling the keys pressed outside of the
+/// alerts (`H`), with the last event it received (`E`).
+pub(crate) struct KeyListener<H, E> {
+    handle: Option<H>,
+    last: Option<E>,
+}
 
-                ev.prevent_default();
+impl<H, E: PartialEq> KeyListener<H, E> {
+    pub(crate) const fn new() -> Self {
+        Self { handle: None, last: None }
+    }
 
-                if ev.shift_key() {
-                    if index == 0 {
-                        index = focusables.len() - 1;
-                    } else {
-                        index -= 1;
-                    }
-                } else {
-                    index = (index + 1) % focusables.len();
-                }
+    /// Adds the listener created by `add`, unless it was already added.
+    pub(crate) fn install(&mut self, add: impl FnOnce() -> H) {
+        if self.handle.is_none() {
+            self.handle = Some(add());
+        }
+    }
 
-                focusables[index]
-                    .focus()
-                    .expect("Could not focus next element");
-            }
+    /// Gives back the listener so as to remove it, and
+    /// forgets the last event so as not to keep it alive.
+    pub(crate) fn uninstall(&mut self) -> Option<H> {
+        self.last = None;
+        self.handle.take()
+    }
+
+    /// Records an event and checks if it wasn't received yet:
+    /// several listeners may receive the same event (see [`init_key_handlers`]).
+    pub(crate) fn is_new(&mut self, ev: E) -> bool {
+        let is_new = self.last.as_ref() != Some(&ev);
+        self.last = Some(ev);
+        is_new
+    }
+}
+
+/// Adds the window listener handling the keys pressed
+/// outside of the alerts (see [`on_window_keydown`]).
+/// It does nothing if the listener was already added.
+fn add_key_listener() {
+    KEY_LISTENER.with_borrow_mut(|listener| {
+        listener.install(|| window_event_listener(ev::keydown, on_window_keydown));
+    });
+}
+
+/// Removes the window listener handling the keys pressed outside of the alerts,
+/// once there is no alert anymore.
+fn remove_key_listener() {
+    if !registry::is_empty() {
+        return;
+    }
+    if let Some(listener) = KEY_LISTENER.with_borrow_mut(KeyListener::uninstall) {
+        listener.remove();
+    }
+}
+
+/// Handles the keys pressed within the given alert (see [`handle_keydown`]).
+/// It's called before the key reaches the rest of the page, so the Escape key
+/// doesn't propagate if the "stop_escape_propagation" option is enabled.
+fn on_swal_keydown(id: &str, ev: &KeyboardEvent) {
+    if registry::topmost().as_deref() != Some(id) {
+        return;
+    }
+    let Some(swal) = get_swal() else {
+        return;
+    };
+    let stop_propagation =
+        registry::with_instance(id, |instance| instance.stop_escape_propagation).unwrap_or(false);
+    if handle_keydown(id, &swal, ev, true) && stop_propagation && ev.code().eq("Escape") {
+        ev.stop_propagation();
+    }
+}
+
+/// Handles the keys pressed outside of the topmost alert, for example when
+/// the alert doesn't have a backdrop and the user is using the rest of the page.
+/// The keys pressed within the alert are handled by [`on_swal_keydown`].
+fn on_window_keydown(ev: KeyboardEvent) {
+    let Some(id) = registry::topmost() else {
+        return;
+    };
+    if !KEY_LISTENER.with_borrow_mut(|listener| listener.is_new(ev.clone())) {
+        return;
+    }
+    let Some(swal) = get_swal() else {
+        return;
+    };
+    if swal.contains(event_origin(&ev).as_ref()) || !concerns(&swal, &ev) {
+        return;
+    }
+    handle_keydown(&id, &swal, &ev, false);
+}
+
+/// Handles a key pressed while the given alert is the topmost one.
+/// Escape dismisses the alert and Tab holds the focus within it.
+/// The other shortcuts (Enter, the arrows and the "key_bindings" option)
+/// only apply to the keys pressed within the alert (`inside`).
+///
+/// The keys that were already handled (`default_prevented`) and
+/// the keys used to compose text (with an IME) are ignored.
+/// It returns `true` if the key was handled by the alert.
+fn handle_keydown(id: &str, swal: &Element, ev: &KeyboardEvent, inside: bool) -> bool {
+    if ev.default_prevented() || ev.is_composing() {
+        return false;
+    }
+    let focused = active_element_of(swal);
+    let is_focused = |selector: &str| {
+        focused
+            .as_ref()
+            .is_some_and(|focused| focused.matches(selector).unwrap_or(false))
+    };
+    // A shortcut that could be typed in a field is ignored
+    // while the user is typing in it.
+    let is_typing = is_focused(EDITABLE_SELECTOR);
+    let Some((auto_close, allow_enter_key, binding)) = registry::with_instance(id, |instance| {
+        let binding = instance
+            .key_bindings
+            .iter()
+            .find(|(combo, _)| combo.matches(ev) && !(is_typing && combo.is_typed()))
+            .map(|(_, action)| *action);
+        (instance.auto_close, instance.allow_enter_key, binding)
+    }) else {
+        return false;
+    };
+    let code = ev.code();
+    if code.eq("Escape") {
+        // The alert has the last word on Escape,
+        // even if it cannot be dismissed.
+        if auto_close {
+            dismiss_id(id, SwalDismissReason::Esc);
         }
-    })
+        return true;
+    }
+    if code.eq("Tab") {
+        return hold_focus(swal, focused, ev);
+    }
+    if !inside {
+        return false;
+    }
+    if let Some(action) = binding {
+        ev.prevent_default();
+        trigger(swal, action);
+        return true;
+    }
+    let has_modifier = ev.ctrl_key() || ev.alt_key() || ev.meta_key();
+    if ev.key().eq("Enter") {
+        // The buttons, the links, and the fields
+        // accepting several lines handle Enter themselves.
+        if !allow_enter_key || has_modifier || ev.repeat() || is_focused(ENTER_HANDLED_SELECTOR) {
+            return false;
+        }
+        ev.prevent_default();
+        trigger(swal, SwalAction::Confirm);
+        return true;
+    }
+    if code.eq("ArrowLeft") || code.eq("ArrowRight") {
+        if has_modifier || ev.shift_key() {
+            return false;
+        }
+        let Some(actions) = query_in::<Element>(swal, ".swal-actions") else {
+            return false;
+        };
+        let buttons = find_focusables(&actions);
+        let Some(current) = buttons.iter().position(|button| {
+            focused
+                .as_ref()
+                .is_some_and(|f| button.is_same_node(Some(f)))
+        }) else {
+            return false;
+        };
+        ev.prevent_default();
+        let index = if code.eq("ArrowLeft") {
+            current.checked_sub(1).unwrap_or(buttons.len() - 1)
+        } else {
+            (current + 1) % buttons.len()
+        };
+        let _ = buttons[index].focus();
+        return true;
+    }
+    false
+}
+
+/// Moves the focus to the next (or previous, with Shift) focusable
+/// element of the alert, so that it doesn't leave a modal alert.
+/// It returns `true` if the focus was moved.
+fn hold_focus(swal: &Element, focused: Option<Element>, ev: &KeyboardEvent) -> bool {
+    // A non-modal popup (without backdrop)
+    // doesn't hold the focus.
+    if swal
+        .get_attribute("aria-modal")
+        .is_none_or(|modal| modal != "true")
+    {
+        return false;
+    }
+
+    let focusables = find_focusables(swal);
+    if focusables.is_empty() {
+        return false;
+    }
+
+    // The focus might not be on one of the focusable elements
+    // (for example if it's on the container itself, or if the
+    // focused element was removed), in which case the first
+    // or the last element receives it.
+    let current = focused.and_then(|active_element| {
+        focusables
+            .iter()
+            .position(|f| f.is_same_node(Some(&active_element)))
+    });
+
+    ev.prevent_default();
+
+    let index = match (current, ev.shift_key()) {
+        (None, false) => 0,
+        (None, true) => focusables.len() - 1,
+        (Some(0), true) => focusables.len() - 1,
+        (Some(i), true) => i - 1,
+        (Some(i), false) => (i + 1) % focusables.len(),
+    };
+
+    let _ = focusables[index].focus();
+    true
+}
+
+/// The elements in which the user can type.
+const EDITABLE_SELECTOR: &str =
+    "input, textarea, select, [contenteditable]:not([contenteditable='false'])";
+
+/// The elements that already do something when Enter is pressed.
+const ENTER_HANDLED_SELECTOR: &str =
+    "button, a[href], textarea, select, [contenteditable]:not([contenteditable='false'])";
+
+/// Clicks the button of the given alert corresponding to the action,
+/// like [`click_confirm`] and the others do.
+fn trigger(swal: &Element, action: SwalAction) {
+    if let Some(button) = query_in::<web_sys::HtmlButtonElement>(swal, action.button_selector()) {
+        button.click();
+    }
+}
+
+/// Checks if a keyboard event concerns the given alert.
+/// When the alert is scoped to its target (see the "scoped_to_target" option),
+/// the events coming from outside of the target are ignored.
+fn concerns(swal: &Element, ev: &KeyboardEvent) -> bool {
+    if !swal.class_list().contains("swal-backdrop-scoped") {
+        return true;
+    }
+    mount_point(swal)
+        .parent_node()
+        .is_some_and(|target| target.contains(event_origin(ev).as_ref()))
+}
+
+/// Gets the node where a keyboard event comes from. The first element
+/// of the composed path is the actual target, even if it's in a shadow root.
+fn event_origin(ev: &KeyboardEvent) -> Option<web_sys::Node> {
+    ev.composed_path().get(0).dyn_into::<web_sys::Node>().ok()
 }
 
 /// Gets the active element, meaning the element that has the focus.
 /// It returns a [`web_sys::HtmlElement`] so as to be able to focus it again.
+#[allow(clippy::manual_ok_err)]
 pub fn get_active_element() -> Option<web_sys::HtmlElement> {
-    let active = document().active_element();
+    let active = try_document().ok()?.active_element();
     if let Some(active) = active {
         match active.dyn_into::<web_sys::HtmlElement>() {
             Ok(valid) => Some(valid),
@@ -162,179 +742,622 @@ pub fn get_active_element() -> Option<web_sys::HtmlElement> {
     }
 }
 
-/// Sets the element that should receive the focus when the Swal closes.
+/// Sets the element that should receive the focus when the current Swal closes.
 pub fn set_previously_focused_element(element: web_sys::HtmlElement) {
-    PREVIOUSLY_FOCUSED.with(|c| *c.borrow_mut() = Some(element));
+    if let Some(id) = registry::topmost() {
+        registry::with_instance(&id, |instance| instance.previously_focused = Some(element));
+    }
 }
 
-/// Forgets the element that should receive the focus when the Swal closes.
+/// Forgets the element that should receive the focus when the current Swal closes.
 pub fn forget_previously_focused_element() {
-    PREVIOUSLY_FOCUSED.with(|c| *c.borrow_mut() = None);
+    if let Some(id) = registry::topmost() {
+        registry::with_instance(&id, |instance| instance.previously_focused = None);
+    }
 }
 
-/// Checks if the Sweet Alert is currently open.
+/// Checks if a Sweet Alert is currently open.
 pub fn is_open() -> bool {
     get_swal().is_some()
 }
 
-/// Closes the alert and returns a boolean indicating if the action was successfull.
+/// Closes the current alert (the topmost one) and returns a boolean
+/// indicating if the action was successfull.
 /// It will return `false` if the alert isn't opened.
 /// It will trigger a copy of the current alert's "then" callback.
 ///
 /// Closing a popup without mentioning a result will not trigger the "then" callback.
+///
+/// The result of a confirmed popup that doesn't give a `bool` (see [`SwalValue`])
+/// gets its value from the input of the popup. Use [`close_with_value`] to give it
+/// another value.
 pub fn close(result: Option<SwalResult>) -> bool {
-    if let Some(then) = THEN_CALLBACK.with_borrow(|t| *t) {
-        if let Some(result) = result {
-            (then)(result);
+    close_any(result.map(SwalResult::into_any))
+}
+
+/// Same as [`close`], but it returns an error instead of logging it.
+/// Note that the alert is still closed and removed from the DOM if possible.
+pub fn try_close(result: Option<SwalResult>) -> Result<bool, SwalError> {
+    try_close_any(result.map(SwalResult::into_any))
+}
+
+/// Closes the alert as if it was confirmed with the given value,
+/// and returns a boolean indicating if the action was successfull.
+/// The "then" callback receives `SwalResult::confirmed_with(value)`.
+///
+/// The value should be of the type expected by the alert (see [`SwalValue`]),
+/// otherwise it's created from the input of the popup instead.
+///
+/// # Example
+///
+/// ```no_run
+/// # use leptos_sweetalert::*;
+///
+/// Swal::fire(SwalOptions::<&str, SwalIcon, String> {
+///     title: "What is your name?",
+///     auto_close: false,
+///     pre_confirm: || {
+///         Swal::close_with_value(String::from("John"));
+///     },
+///     then: |result: SwalResult<String>| {
+///         assert_eq!(result.into_confirmed().as_deref(), Some("John"));
+///     },
+///     ..SwalOptions::typed()
+/// });
+/// ```
+pub fn close_with_value<T: 'static>(value: T) -> bool {
+    close_any(Some(SwalResult::confirmed_with(value).into_any()))
+}
+
+/// Same as [`close_with_value`], but it returns an error instead of logging it.
+pub fn try_close_with_value<T: 'static>(value: T) -> Result<bool, SwalError> {
+    try_close_any(Some(SwalResult::confirmed_with(value).into_any()))
+}
+
+/// Dismisses the alert with [`SwalDismissReason::Programmatic`],
+/// and returns a boolean indicating if the action was successfull.
+/// Unlike `close(None)`, it triggers the "then" callback.
+pub fn dismiss() -> bool {
+    close(Some(SwalResult::canceled(SwalDismissReason::Programmatic)))
+}
+
+/// Closes the topmost alert with a result whose type was erased,
+/// and logs the errors.
+fn close_any(result: Option<AnyResult>) -> bool {
+    try_close_any(result).unwrap_or_else(|error| {
+        log_error("Could not close the Swal", &error);
+        false
+    })
+}
+
+/// Closes the topmost alert with a result whose type was erased.
+fn try_close_any(result: Option<AnyResult>) -> Result<bool, SwalError> {
+    match registry::topmost() {
+        Some(id) => try_close_id(&id, result),
+        None => Ok(false),
+    }
+}
+
+/// Closes the alert with the given ID, and logs the errors.
+/// It returns `false` if this alert isn't opened.
+pub(crate) fn close_id(id: &str, result: Option<AnyResult>) -> bool {
+    try_close_id(id, result).unwrap_or_else(|error| {
+        log_error("Could not close the Swal", &error);
+        false
+    })
+}
+
+/// Dismisses the alert with the given ID for the given reason.
+/// It returns `false` if this alert isn't opened.
+fn dismiss_id(id: &str, reason: SwalDismissReason) -> bool {
+    close_id(id, Some(SwalResult::<bool>::canceled(reason).into_any()))
+}
+
+/// Closes the alert with the given ID, whether it's the topmost one or not.
+fn try_close_id(id: &str, result: Option<AnyResult>) -> Result<bool, SwalError> {
+    // The timeouts and listeners of the alert are canceled by the transition.
+    let closing = registry::with_instance(id, |instance| {
+        instance.transition(SwalState::Closing).then(|| {
+            (
+                instance.resolver.take(),
+                instance.root.clone(),
+                instance.parent.clone(),
+                instance.previously_focused.take(),
+            )
+        })
+    });
+    let Some((resolver, root, parent, previously_focused)) = closing.flatten() else {
+        return Ok(false);
+    };
+    // The alert is closing before its callback is called,
+    // in case the callback opens another alert.
+    if let Some(resolve) = resolver {
+        let resolving = RESOLVING.replace(Some(id.to_string()));
+        resolve(result);
+        RESOLVING.set(resolving);
+    }
+    call_hook(id, SwalHook::WillClose);
+    let Some(swal) = root else {
+        if let Some(instance) = remove(id) {
+            destroy(instance);
         }
-        THEN_CALLBACK.with(|c| *c.borrow_mut() = None);
-        AUTO_CLOSE.with(|a| *a.borrow_mut() = true);
+        return Ok(true);
+    };
+    // Here the goal is to remove the swal from the DOM
+    // as soon as the closing transition is over.
+    let popup = get_popup(&swal);
+    // Even if it fails, the Swal gets removed.
+    let hidden = swal.set_attribute("aria-hidden", "true");
+    apply_hide_class(&popup);
+    unwatch_focus(&swal);
+    restore_siblings(&swal);
+    set_stacked(&swal, false);
+    // The alert below this one comes back,
+    // unless it was closed in the meantime.
+    let parent = parent.filter(|parent| registry::is_active(parent));
+    if let Some(parent_root) = parent.as_deref().and_then(get_root) {
+        set_stacked(&parent_root, false);
     }
-    if let Some(swal) = get_swal() {
-        // Here the goal is to remove the swal from the DOM
-        // as soon as the ending transition is over.
-        // My solution is to extract the transition duration
-        // from the computed styles and remove the node in a
-        // delayed closure (via set_timeout from leptos).
-        //
-        // Initially I was going to listen to the "transitionend" event,
-        // but WebAssembly's only solution in my case would leak memory,
-        // as they so gently explain here:
-        // https://rustwasm.github.io/wasm-bindgen/examples/closures.html#srclibrs
-        // (which is awful and dumb)
-        swal.set_attribute("aria-hidden", "true")
-            .expect("Could not change the Swal's aria-hidden attribute.");
-        set_timeout(
-            || {
-                if let Some(swal) = get_swal() {
-                    swal.remove()
-                }
-            },
-            Duration::from_secs_f32(get_transition_duration(&swal)),
-        );
-        PREVIOUSLY_FOCUSED.with(|c| {
-            let elt = c.borrow();
-            if elt.is_some() {
-                let _ = elt.as_ref().unwrap().focus();
-                drop(elt); // to avoid double borrow
-                *c.borrow_mut() = None;
+    // The scroll remains locked as long as another modal alert is opened.
+    if !registry::any_locks_scroll() {
+        if let Err(error) = unlock_scroll() {
+            log_error("Could not unlock the scroll of the page", &error);
+        }
+    }
+    let removed = swal.clone();
+    let removed_id = id.to_string();
+    when_animations_end(&swal, move || {
+        // This specific element is removed, even if
+        // other alerts were opened in the meantime.
+        // The host of the shadow root is removed too.
+        let mounted = mount_point(&removed);
+        // Another alert may still be scoped to the same target.
+        if let Some(target) = mounted.parent_element() {
+            if !registry::is_target_used(&target, &removed_id) {
+                let _ = target.class_list().remove_1("swal-scoped-target");
             }
-        });
-        true
-    } else {
-        false
+        }
+        mounted.remove();
+        if let Some(instance) = remove(&removed_id) {
+            destroy(instance);
+        }
+    });
+    // The element may have been removed from the page
+    // while the Swal was opened, in which case it
+    // cannot receive the focus anymore. It may also be
+    // in an alert that is closing (a parent closed by its
+    // child), which already gave the focus back.
+    if let Some(elt) = previously_focused {
+        if elt.is_connected() && !registry::is_in_closing_alert(&elt) {
+            let _ = elt.focus();
+        }
+    }
+    hidden?;
+    Ok(true)
+}
+
+/// Makes the given alert go from [`SwalState::Closing`] to [`SwalState::Closed`]
+/// once it was removed from the DOM, and removes it from the registry.
+/// It returns `None` if the alert wasn't closing.
+fn remove(id: &str) -> Option<SwalInstance> {
+    let closed = registry::with_instance(id, |instance| instance.transition(SwalState::Closed));
+    if closed != Some(true) {
+        return None;
+    }
+    let instance = registry::unregister(id);
+    remove_key_listener();
+    instance
+}
+
+/// Disposes the reactive owner of an alert that was removed
+/// (the signals, effects and event listeners of the alert),
+/// then calls its last lifecycle hooks.
+fn destroy(mut instance: SwalInstance) {
+    drop(instance.owner.take());
+    run_hook(&mut instance, SwalHook::DidClose);
+    run_hook(&mut instance, SwalHook::DidDestroy);
+}
+
+/// Calls a lifecycle hook of an alert that isn't in the registry,
+/// unless it was already called (see [`SwalInstance::next_hook`]).
+fn run_hook(instance: &mut SwalInstance, hook: SwalHook) {
+    if let Some((hook, popup)) = instance.next_hook(hook) {
+        hook(&popup);
+    }
+}
+
+/// Calls a lifecycle hook of the given alert, unless it was already called
+/// (see [`SwalInstance::next_hook`]). The hook is called outside of the registry,
+/// since it may open or close alerts.
+pub(crate) fn call_hook(id: &str, hook: SwalHook) {
+    if let Some((hook, popup)) = registry::with_instance(id, |i| i.next_hook(hook)).flatten() {
+        hook(&popup);
     }
 }
 
-/// Gets the WebSys Element for the popup from the DOM.
+/// Gets the WebSys Element for the current popup (the topmost one) from the DOM.
+/// It's found even if it was added to a shadow root (see the "target" option).
 pub fn get_swal() -> Option<Element> {
-    document().get_element_by_id("swal")
-}
-
-/// Gets the WebSys HtmlCollection for the confirmation button from the DOM.
-/// It returns an HtmlCollection because the button has a class, and by
-/// definition a class can be attached to several elements, therefore
-/// there is no other way that returning an HtmlCollection.
-/// If things are done right, then this should return a
-/// collection of one element, and this element should be
-/// the expected button.
-pub fn get_confirm_button() -> HtmlCollection {
-    document().get_elements_by_class_name("swal-confirm-button")
-}
-
-/// Gets the WebSys HtmlCollection for the "deny" button from the DOM.
-/// It returns an HtmlCollection because the button has a class, and by
-/// definition a class can be attached to several elements, therefore
-/// there is no other way that returning an HtmlCollection.
-/// If things are done right, then this should return a
-/// collection of one element, and this element should be
-/// the expected button.
-pub fn get_deny_button() -> HtmlCollection {
-    document().get_elements_by_class_name("swal-deny-button")
-}
-
-/// Gets the WebSys HtmlCollection for the "cancel" button from the DOM.
-/// It returns an HtmlCollection because the button has a class, and by
-/// definition a class can be attached to several elements, therefore
-/// there is no other way that returning an HtmlCollection.
-/// If things are done right, then this should return a
-/// collection of one element, and this element should be
-/// the expected button.
-pub fn get_cancel_button() -> HtmlCollection {
-    document().get_elements_by_class_name("swal-cancel-button")
-}
-
-/// Gets the focusable buttons in the Swal.
-pub fn get_focusables() -> Vec<web_sys::HtmlElement> {
-    let mut vec = Vec::new();
-    if let Some(swal) = get_swal() {
-        let all = swal
-                .query_selector_all("*:is(a[href], button, input, textarea, select, details):not([disabled]):not([aria-hidden=true]):not([inert]), [tabindex]:not([tabindex='-1'])")
-                .expect("Could not retrieve the focusable elements");
-        for i in 0..all.length() {
-            let el = all
-                .get(i)
-                .unwrap()
-                .dyn_into::<web_sys::HtmlElement>()
-                .unwrap();
-            if !has_display_none(&el) {
-                vec.push(el);
-            }
+    registry::topmost_root().filter(|swal| swal.is_connected())
+}
+
+/// Gets the popup (the `.swal-container` element) of the given Swal.
+/// This is the element given to the lifecycle hooks.
+fn get_popup(swal: &Element) -> Element {
+    swal.query_selector(".swal-container")
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| swal.clone())
+}
+
+/// Replaces the classes animating the opening of the popup
+/// by the ones animating its closing (see the "show_class"
+/// and "hide_class" options).
+fn apply_hide_class(popup: &Element) {
+    let class_list = popup.class_list();
+    let _ = class_list.remove_1("swal-shake");
+    if let Some(show_classes) = popup.get_attribute("data-swal-show-class") {
+        for class in show_classes.split_whitespace() {
+            let _ = class_list.remove_1(class);
         }
     }
-    vec
-}
-
-fn has_display_none(element: &web_sys::HtmlElement) -> bool {
-    element
-        .style()
-        .get_property_value("display")
-        .unwrap_or("none".to_string())
-        == "none"
-}
-
-/// Gets the value of the "transition-duration" CSS property.
-/// It is used to remove the Swal from the DOM once the animation is over.
-fn get_transition_duration(el: &Element) -> f32 {
-    let duration = TRANSITION_DURATION.with_borrow(|t| *t);
-    if duration == -1.0 {
-        let css_value = window()
-            .expect("Could not get window")
-            .get_computed_style(&el)
-            .expect("Could not get computed style of Swal")
-            .expect("Could not get computed style of Swal")
-            .get_property_value("transition-duration");
-        if let Ok(css_value) = css_value {
-            let result = css_value
-                .get(0..css_value.len() - 1)
-                .expect("Invalid CSS value for transition duration of Swal")
-                .parse::<f32>()
-                .expect("Could not parse transition duration of Swal");
-            TRANSITION_DURATION.with(|t| *t.borrow_mut() = result);
-            result
-        } else {
-            0.0
+    if let Some(hide_classes) = popup.get_attribute("data-swal-hide-class") {
+        for class in hide_classes.split_whitespace() {
+            let _ = class_list.add_1(class);
         }
+    }
+}
+
+/// Shakes the popup, for example to indicate that
+/// the value of an input is invalid.
+/// Does nothing if no alert is opened.
+pub fn shake() {
+    if let Some(swal) = get_swal() {
+        let popup = get_popup(&swal);
+        let class_list = popup.class_list();
+        let _ = class_list.remove_1("swal-shake");
+        // Reading the size of the popup forces the browser to
+        // apply the removal of the class, so the animation
+        // starts again even if the popup is already shaking.
+        if let Some(popup) = popup.dyn_ref::<web_sys::HtmlElement>() {
+            let _ = popup.offset_width();
+        }
+        let _ = class_list.add_1("swal-shake");
+    }
+}
+
+/// Finds an element in the current alert and casts it to the expected type.
+fn query_swal<T: JsCast>(selector: &str) -> Option<T> {
+    query_in(&get_swal()?, selector)
+}
+
+/// Finds an element in the given alert and casts it to the expected type.
+fn query_in<T: JsCast>(swal: &Element, selector: &str) -> Option<T> {
+    swal.query_selector(selector).ok()??.dyn_into::<T>().ok()
+}
+
+/// Gets the confirmation button of the current alert.
+pub fn get_confirm_button() -> Option<web_sys::HtmlButtonElement> {
+    query_swal(".swal-confirm-button")
+}
+
+/// Gets the deny button of the current alert.
+pub fn get_deny_button() -> Option<web_sys::HtmlButtonElement> {
+    query_swal(".swal-deny-button")
+}
+
+/// Gets the cancel button of the current alert.
+pub fn get_cancel_button() -> Option<web_sys::HtmlButtonElement> {
+    query_swal(".swal-cancel-button")
+}
+
+/// Gets the close button (the cross in the corner) of the current alert
+/// (see the "show_close_button" option).
+pub fn get_close_button() -> Option<web_sys::HtmlButtonElement> {
+    query_swal(".swal-close-button")
+}
+
+/// Gets the title of the current alert.
+pub fn get_title() -> Option<web_sys::HtmlElement> {
+    query_swal(".swal-title")
+}
+
+/// Gets the element displaying the text (the description) of the current alert.
+/// Note that it's hidden when the text is empty.
+pub fn get_html_container() -> Option<web_sys::HtmlElement> {
+    query_swal(".swal-text")
+}
+
+/// Gets the element containing the icon of the current alert.
+pub fn get_icon() -> Option<web_sys::HtmlElement> {
+    query_swal(".swal-container-icon")
+}
+
+/// Gets the element containing the buttons of the current alert.
+pub fn get_actions() -> Option<web_sys::HtmlElement> {
+    query_swal(".swal-actions")
+}
+
+/// Gets the footer of the current alert (see the "footer" option).
+pub fn get_footer() -> Option<web_sys::HtmlElement> {
+    query_swal(".swal-footer")
+}
+
+/// Gets the first input, select or textarea of the current alert,
+/// for example the one given via the "body" option.
+pub fn get_input() -> Option<web_sys::HtmlElement> {
+    get_input_of(&get_swal()?)
+}
+
+/// Gets the first input, select or textarea of the given alert.
+fn get_input_of(swal: &Element) -> Option<web_sys::HtmlElement> {
+    query_in(swal, ".swal-container :is(input, select, textarea)")
+}
+
+/// Gets the element displaying the validation message of the
+/// current alert (see [`show_validation_message`]).
+pub fn get_validation_message() -> Option<web_sys::HtmlElement> {
+    query_swal(".swal-validation-message")
+}
+
+/// Gets the progress bar of the timer of the current alert,
+/// if it displays one.
+pub fn get_timer_progress_bar() -> Option<web_sys::HtmlElement> {
+    query_swal(".swal-timer-progress-bar")
+}
+
+/// Displays a message below the body of the current alert,
+/// for example to explain why the value of an input is invalid.
+/// The message is announced by screen readers.
+pub fn show_validation_message(message: &str) {
+    if let Some(swal) = get_swal() {
+        set_validation_message(&swal, Some(message));
+    }
+}
+
+/// Hides the message displayed by [`show_validation_message`].
+pub fn reset_validation_message() {
+    if let Some(swal) = get_swal() {
+        set_validation_message(&swal, None);
+    }
+}
+
+/// Displays or hides the validation message of the given alert.
+pub(crate) fn set_validation_message(swal: &Element, message: Option<&str>) {
+    if let Some(el) = query_in::<web_sys::HtmlElement>(swal, ".swal-validation-message") {
+        el.set_text_content(message);
+        let _ = match message {
+            Some(_) => el.remove_attribute("hidden"),
+            None => el.set_attribute("hidden", ""),
+        };
+    }
+}
+
+/// Clicks the confirmation button, exactly as if the user did it:
+/// `pre_confirm`, `then` and `auto_close` behave the same way.
+/// Does nothing if there is no such button or if it's disabled.
+pub fn click_confirm() {
+    if let Some(button) = get_confirm_button() {
+        button.click();
+    }
+}
+
+/// Clicks the deny button, exactly as if the user did it:
+/// `pre_deny`, `then` and `auto_close` behave the same way.
+/// Does nothing if there is no such button or if it's disabled.
+pub fn click_deny() {
+    if let Some(button) = get_deny_button() {
+        button.click();
+    }
+}
+
+/// Clicks the cancel button, exactly as if the user did it:
+/// `then` and `auto_close` behave the same way.
+/// Does nothing if there is no such button or if it's disabled.
+pub fn click_cancel() {
+    if let Some(button) = get_cancel_button() {
+        button.click();
+    }
+}
+
+/// Disables the confirmation, deny and cancel buttons,
+/// so that the user cannot click them anymore.
+pub fn disable_buttons() {
+    if let Some(swal) = get_swal() {
+        set_buttons_disabled(&swal, true);
+    }
+}
+
+/// Enables the confirmation, deny and cancel buttons
+/// (see [`disable_buttons`]).
+pub fn enable_buttons() {
+    if let Some(swal) = get_swal() {
+        set_buttons_disabled(&swal, false);
+    }
+}
+
+/// Disables or enables the confirmation, deny and cancel
+/// buttons of the given alert (those that are displayed).
+/// The buttons disabled via [`SwalContext::set_confirm_enabled`](crate::SwalContext::set_confirm_enabled)
+/// remain disabled.
+fn set_buttons_disabled(swal: &Element, disabled: bool) {
+    let buttons = [".swal-confirm-button", ".swal-deny-button", ".swal-cancel-button"]
+        .into_iter()
+        .filter_map(|selector| query_in::<web_sys::HtmlButtonElement>(swal, selector));
+    disable_buttons_of(buttons, disabled);
+}
+
+/// A button of an alert, as [`set_buttons_disabled`] sees it.
+pub(crate) trait SwalButton {
+    /// Checks if the button was disabled via
+    /// [`SwalContext::set_confirm_enabled`](crate::SwalContext::set_confirm_enabled).
+    fn is_locked(&self) -> bool;
+
+    fn set_disabled(&self, disabled: bool);
+}
+
+impl SwalButton for web_sys::HtmlButtonElement {
+    fn is_locked(&self) -> bool {
+        self.has_attribute("data-swal-disabled")
+    }
+
+    fn set_disabled(&self, disabled: bool) {
+        web_sys::HtmlButtonElement::set_disabled(self, disabled);
+    }
+}
+
+/// Disables or enables the given buttons, except
+/// those that are locked, which remain disabled.
+pub(crate) fn disable_buttons_of<B: SwalButton>(buttons: impl IntoIterator<Item = B>, disabled: bool) {
+    for button in buttons {
+        button.set_disabled(disabled || button.is_locked());
+    }
+}
+
+/// Shows a loader in place of the confirmation button and disables
+/// all the buttons, for example while performing an asynchronous action.
+/// Does nothing if no alert is opened.
+pub fn show_loading() {
+    if let Some(swal) = get_swal() {
+        set_loading(&swal, true);
+    }
+}
+
+/// Hides the loader displayed by [`show_loading`]
+/// and enables the buttons again.
+pub fn hide_loading() {
+    if let Some(swal) = get_swal() {
+        set_loading(&swal, false);
+    }
+}
+
+/// Shows or hides the loader of the given alert.
+fn set_loading(swal: &Element, loading: bool) {
+    let popup = get_popup(swal);
+    if loading {
+        let _ = popup.class_list().add_1("swal-loading");
+        let _ = popup.set_attribute("aria-busy", "true");
     } else {
-        duration
+        let _ = popup.class_list().remove_1("swal-loading");
+        let _ = popup.remove_attribute("aria-busy");
     }
+    set_buttons_disabled(swal, loading);
+}
+
+/// Checks if the loader is displayed (see [`show_loading`]).
+pub fn is_loading() -> bool {
+    get_swal().is_some_and(|swal| get_popup(&swal).class_list().contains("swal-loading"))
 }
 
-fn SwalComponent<S, I>(opt: SwalOptions<S, I>) -> HtmlElement<AnyElement>
+/// Gets the focusable elements in the Swal, in the order in which
+/// the Tab key visits them (positive "tabindex" values first).
+///
+/// An element is ignored if it's disabled, inert, hidden (computed
+/// "display" or "visibility", `hidden` ancestor, empty box) or
+/// if it has a negative "tabindex".
+pub fn get_focusables() -> Vec<web_sys::HtmlElement> {
+    if let Some(swal) = get_swal() {
+        find_focusables(&swal)
+    } else {
+        Vec::new()
+    }
+}
+
+/// What the confirmation button of the given alert does once clicked
+/// (see [`click_confirm`]): it runs `pre_confirm`, then closes the alert
+/// with the confirmed value, unless an alert was opened above it.
+pub(crate) fn confirm_clicked<T: SwalValue>(
+    id: &str,
+    pre_confirm: fn(),
+    async_pre_confirm: Option<fn() -> SwalFuture<T>>,
+    auto_close: bool,
+    context: &SwalContext<T>,
+) {
+    pre_confirm();
+    // An alert may have been opened above this one,
+    // in which case this one remains opened.
+    if registry::has_child(id) {
+        return;
+    }
+    let swal = get_root(id);
+    if let Some(async_pre_confirm) = async_pre_confirm {
+        let Some(swal) = swal else {
+            return;
+        };
+        set_loading(&swal, true);
+        let id = id.to_string();
+        spawn_local(async move {
+            let value = async_pre_confirm().await;
+            // The alert may have been closed in the meantime.
+            if !registry::is_active(&id) {
+                return;
+            }
+            set_loading(&swal, false);
+            if let Some(value) = value {
+                if auto_close {
+                    close_id(&id, Some(SwalResult::confirmed_with(value).into_any()));
+                }
+            }
+        });
+    } else if auto_close {
+        let value = context
+            .value()
+            .unwrap_or_else(|| T::from_input(swal.and_then(|swal| get_input_of(&swal)).as_ref()));
+        close_id(id, Some(SwalResult::confirmed_with(value).into_any()));
+    };
+}
+
+/// What the deny button of the given alert does once clicked
+/// (see [`click_deny`]): it runs `pre_deny`, then closes the
+/// alert, unless an alert was opened above it.
+pub(crate) fn deny_clicked<T: SwalValue>(id: &str, pre_deny: fn(), auto_close: bool) {
+    pre_deny();
+    if auto_close && !registry::has_child(id) {
+        close_id(id, Some(SwalResult::<T>::denied().into_any()));
+    };
+}
+
+/// What the cancel and close buttons of the given alert do once clicked
+/// (see [`click_cancel`]). When `auto_close` is off, the alert
+/// remains opened and `then` is called directly.
+pub(crate) fn dismiss_clicked<T: SwalValue>(
+    id: &str,
+    reason: SwalDismissReason,
+    auto_close: bool,
+    then: fn(SwalResult<T>),
+) {
+    if auto_close {
+        close_id(id, Some(SwalResult::<T>::canceled(reason).into_any()));
+    } else {
+        then(SwalResult::canceled(reason));
+    };
+}
+
+fn SwalComponent<S, I, T>(opt: SwalOptions<S, I, T>, id: String) -> HtmlElement<AnyElement>
 where
     S: AsRef<str> + Clone + Default + leptos::IntoView + 'static,
     I: SwalIconLike + Default + Clone + Copy + 'static,
+    T: SwalValue,
 {
     let swal_container_ref = create_node_ref::<Div>();
+    let title_id = format!("{}-title", id);
+
+    let context = SwalContext::<T>::new(id.clone(), swal_container_ref);
+    let confirm_enabled = context.is_confirm_enabled();
+    provide_context(context.clone());
 
-    let on_backdrop_clicked = move |ev: MouseEvent| {
-        if let Some(container) = swal_container_ref.get() {
-            if let Some(target) = ev.target() {
-                let actual_target = target.dyn_ref::<web_sys::HtmlElement>();
-                if actual_target.is_some() {
-                    if !container.contains(Some(actual_target.unwrap())) {
-                        if AUTO_CLOSE.with_borrow(|a| *a) {
-                            close(Some(SwalResult::canceled(SwalDismissReason::Backdrop)));
+    let auto_close = opt.auto_close;
+
+    // The backdrop and the buttons close this alert, even if it's not the topmost one.
+    let on_backdrop_clicked = {
+        let id = id.clone();
+        move |ev: MouseEvent| {
+            if let Some(container) = swal_container_ref.get() {
+                if let Some(target) = ev.target() {
+                    if let Some(actual_target) = target.dyn_ref::<web_sys::HtmlElement>() {
+                        if !container.contains(Some(actual_target)) && auto_close {
+                            let result = SwalResult::<T>::canceled(SwalDismissReason::Backdrop);
+                            close_id(&id, Some(result.into_any()));
                         }
                     }
                 }
@@ -342,75 +1365,102 @@ where
         }
     };
 
-    let then_callback = opt.then.clone();
-    let auto_close = opt.auto_close.clone();
+    let is_scoped = opt.is_scoped();
+    let is_modal = opt.backdrop.is_modal();
+    let backdrop_background = match &opt.backdrop {
+        SwalBackdrop::Custom(background) => Some(background.clone()),
+        _ => None,
+    };
+    let width = css_override(&opt.width);
+    // The limits of the stylesheets don't apply to the given width,
+    // but the popup doesn't overflow the screen.
+    let min_width = width.as_ref().map(|_| "0");
+    let max_width = width.as_ref().map(|_| "100%");
+    let padding = css_override(&opt.padding);
+    let color = css_override(&opt.color);
+    let background = css_override(&opt.background);
+
+    let show_classes = opt.show_class.show_classes().to_string();
+    let hide_classes = opt.hide_class.hide_classes().to_string();
 
     let has_icon = opt.icon.is_defined();
     let has_text = opt.has_text();
     let has_confirm_btn_text = opt.has_confirm_button_text();
     let has_deny_btn_text = opt.has_deny_button_text();
     let has_cancel_btn_text = opt.has_cancel_button_text();
+    let has_footer = opt.has_footer();
+    let timer_progress_bar_duration = opt
+        .timer
+        .filter(|_| opt.timer_progress_bar)
+        .map(|timer| format!("{}ms", timer.as_millis()));
 
-    // Here we copy the "then" callback and store it as a static variable.
-    // The point of doing this is that it's the only way to detect whether or not
-    // the Escape key was pressed (or if the backdrop was clicked) when closing the
-    // alert. We need a way to execute this callback, and since it cannot be a
-    // reference, a copy does the trick just fine.
-    THEN_CALLBACK.with(move |t| *t.borrow_mut() = Some(then_callback));
-
-    // We need to know if the developer has allowed
-    // the Escape key and the backdrop to close the popup.
-    AUTO_CLOSE.with(move |a| *a.borrow_mut() = auto_close);
-
-    let on_confirm = move |_| {
-        (opt.pre_confirm)();
-        if opt.auto_close {
-            (opt.then)(SwalResult::confirmed());
-            close(None);
-        };
+    let on_confirm = {
+        let id = id.clone();
+        let context = context.clone();
+        move |_| confirm_clicked(&id, opt.pre_confirm, opt.async_pre_confirm, auto_close, &context)
     };
 
-    let on_deny = move |_| {
-        (opt.pre_deny)();
-        if opt.auto_close {
-            (opt.then)(SwalResult::denied());
-            close(None);
-        };
+    let on_deny = {
+        let id = id.clone();
+        move |_| deny_clicked::<T>(&id, opt.pre_deny, auto_close)
     };
 
-    let on_cancel = move |_| {
-        (opt.then)(SwalResult::canceled(SwalDismissReason::Cancel));
-        if opt.auto_close {
-            close(None);
-        };
+    let on_cancel = {
+        let id = id.clone();
+        move |_| dismiss_clicked(&id, SwalDismissReason::Cancel, auto_close, opt.then)
+    };
+
+    let on_close = {
+        let id = id.clone();
+        move |_| dismiss_clicked(&id, SwalDismissReason::Close, auto_close, opt.then)
     };
 
     (view! {
         <div
             role="dialog"
-            aria-modal="true"
-            aria-labelledby="swal-title"
-            id="swal"
+            aria-modal=if is_modal && !is_scoped { "true" } else { "false" }
+            aria-labelledby=title_id.clone()
+            id=id.clone()
             on:click=on_backdrop_clicked
-            class="swal-backdrop"
-            class:swal-no-animation={!opt.animation}
+            on:keydown:undelegated=move |ev: KeyboardEvent| on_swal_keydown(&id, &ev)
+            class=format!("swal-backdrop swal-position-{}", opt.position)
+            class:swal-no-animation={!opt.animation.is_enabled()}
+            class:swal-backdrop-none={!is_modal}
+            class:swal-backdrop-scoped=is_scoped
+            style:background=backdrop_background
             aria-hidden="true"
         >
-            <div _ref=swal_container_ref class="swal-container">
+            <div
+                _ref=swal_container_ref
+                tabindex="-1"
+                class=format!("swal-container swal-grow-{} {}", opt.grow, show_classes)
+                data-swal-show-class=css_override(&show_classes)
+                data-swal-hide-class=css_override(&hide_classes)
+                style:width=width
+                style:min-width=min_width
+                style:max-width=max_width
+                style:padding=padding
+                style:background=background
+            >
                 <Show when=move || has_icon>
                     <div class="swal-container-icon fade-icon">
                         {opt.icon.get_icon_element()}
                     </div>
                 </Show>
-                <strong id="swal-title">{opt.title}</strong>
-                <Show when=move || has_text>
-                    <p>{opt.text.clone()}</p>
-                </Show>
-                {opt.body}
-                <div>
+                <strong id=title_id class="swal-title" style:color=color.clone()>{opt.title}</strong>
+                <p class="swal-text" hidden=!has_text style:color=color>{opt.text}</p>
+                {opt.body.run()}
+                <div class="swal-validation-message" role="alert" hidden></div>
+                <div class="swal-actions">
                     {match opt.show_confirm_button {
                         true => view! {
-                            <button type="button" class="swal-confirm-button" on:click=on_confirm>
+                            <button
+                                type="button"
+                                class="swal-confirm-button"
+                                prop:disabled=move || !confirm_enabled.get()
+                                attr:data-swal-disabled=move || (!confirm_enabled.get()).then_some("")
+                                on:click=on_confirm
+                            >
                                 <Show when=move || { has_confirm_btn_text } fallback=|| view! { "Ok" }>
                                     { opt.confirm_button_text.clone() }
                                 </Show>
@@ -439,10 +1489,24 @@ where
                         false => view! {}.into_view(),
                     }}
                 </div>
+                <Show when=move || has_footer>
+                    <div class="swal-footer">{opt.footer.clone()}</div>
+                </Show>
+                {timer_progress_bar_duration.map(|duration| view! {
+                    <div class="swal-timer-progress-bar-container">
+                        <div class="swal-timer-progress-bar" style:animation-duration=duration></div>
+                    </div>
+                })}
+                {match opt.show_close_button {
+                    true => view! {
+                        <button type="button" class="swal-close-button" aria-label="Close" on:click=on_close>
+                            "×"
+                        </button>
+                    }.into_view(),
+                    false => view! {}.into_view(),
+                }}
             </div>
         </div>
     })
-    .into_view()
-    .into_html_element()
-    .expect("Could not create Swal component")
+    .into_any()
 }
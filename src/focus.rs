@@ -0,0 +1,199 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use leptos::*;
+use web_sys::js_sys::Array;
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::{window, Element, MutationObserver, MutationObserverInit};
+
+/// The elements that can potentially receive the focus.
+/// They are then filtered by [`is_focusable`], because a selector
+/// cannot know whether an element is actually rendered or not.
+const FOCUSABLE_SELECTOR: &str = "a[href], area[href], button, input:not([type='hidden']), textarea, select, details > summary, iframe, audio[controls], video[controls], [contenteditable]:not([contenteditable='false']), [tabindex]";
+
+thread_local! {
+    /// The watchers of each opened Swal, recovering the focus
+    /// when the focused element gets removed (see [`watch_focus`]).
+    static FOCUS_WATCHERS: RefCell<Vec<FocusWatcher>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Watches the content of a Swal. The closures must be kept
+/// alive as long as the observer and the listener are connected.
+struct FocusWatcher {
+    swal: Element,
+    observer: MutationObserver,
+    _on_mutation: Closure<dyn FnMut(Array, MutationObserver)>,
+    on_focusin: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+/// Gets the elements within `root` that can receive the focus
+/// via the keyboard, in the order in which the Tab key visits them:
+/// first the elements with a positive "tabindex" (in ascending order),
+/// then all the others in the order of the document.
+pub(crate) fn find_focusables(root: &Element) -> Vec<web_sys::HtmlElement> {
+    let mut candidates = Vec::new();
+    if let Ok(all) = root.query_selector_all(FOCUSABLE_SELECTOR) {
+        for i in 0..all.length() {
+            let Some(el) = all
+                .get(i)
+                .and_then(|node| node.dyn_into::<web_sys::HtmlElement>().ok())
+            else {
+                continue;
+            };
+            if is_focusable(&el) {
+                candidates.push((el.tab_index(), el));
+            }
+        }
+    }
+    sort_by_tab_index(candidates)
+}
+
+/// Sorts the elements the same way the browser does when pressing Tab.
+/// Elements with a positive tab index come first, in ascending order,
+/// followed by the other ones, whose order remains unchanged.
+pub(crate) fn sort_by_tab_index<T>(mut candidates: Vec<(i32, T)>) -> Vec<T> {
+    // The sort is stable, therefore the
+    // order of the document is preserved.
+    candidates.sort_by_key(|(tab_index, _)| match *tab_index {
+        i if i > 0 => i,
+        _ => i32::MAX,
+    });
+    candidates.into_iter().map(|(_, el)| el).collect()
+}
+
+/// Checks whether an element can actually receive the focus.
+/// It must not be disabled, inert, hidden from the accessibility tree,
+/// removed from the sequential navigation (`tabindex="-1"`), and it
+/// must be rendered (computed "display" and "visibility", non-empty box).
+fn is_focusable(el: &web_sys::HtmlElement) -> bool {
+    if el.tab_index() < 0 {
+        return false;
+    }
+    if el.matches(":disabled").unwrap_or(false) {
+        return false;
+    }
+    if el
+        .closest("[hidden], [inert], [aria-hidden='true']")
+        .ok()
+        .flatten()
+        .is_some()
+    {
+        return false;
+    }
+    // An element hidden by "display: none" (or inside such an element)
+    // doesn't generate any box, and so neither does an element with
+    // "display: contents".
+    let rects = el.get_client_rects();
+    if rects.length() == 0 {
+        return false;
+    }
+    let rect = el.get_bounding_client_rect();
+    if rect.width() == 0.0 && rect.height() == 0.0 {
+        return false;
+    }
+    if let Some(style) = window().and_then(|w| w.get_computed_style(el).ok().flatten()) {
+        let visibility = style.get_property_value("visibility").unwrap_or_default();
+        if visibility == "hidden" || visibility == "collapse" {
+            return false;
+        }
+    }
+    true
+}
+
+/// Gets the focused element of the document or of the shadow root
+/// containing the given element. Unlike `document.activeElement`,
+/// it doesn't stop at the host of the shadow root.
+pub(crate) fn active_element_of(el: &Element) -> Option<Element> {
+    let root = el.get_root_node();
+    if let Some(shadow_root) = root.dyn_ref::<web_sys::ShadowRoot>() {
+        shadow_root.active_element()
+    } else {
+        root.dyn_ref::<web_sys::Document>()?.active_element()
+    }
+}
+
+/// Focuses the first focusable element of the Swal,
+/// or the container itself if there is none.
+pub(crate) fn focus_first(swal: &Element) {
+    if let Some(first) = find_focusables(swal).first() {
+        let _ = first.focus();
+    } else if let Some(container) = swal
+        .query_selector(".swal-container")
+        .ok()
+        .flatten()
+        .and_then(|c| c.dyn_into::<web_sys::HtmlElement>().ok())
+    {
+        let _ = container.focus();
+    }
+}
+
+/// Watches the content of the Swal so that the focus goes
+/// back into the popup if the focused element gets removed.
+/// Only the element of the popup that had the focus last is concerned:
+/// the focus isn't taken back if the user moved it somewhere else.
+pub(crate) fn watch_focus(swal: &Element) {
+    // The last element of the popup that received the focus.
+    let last_focused: Rc<RefCell<Option<Element>>> = Rc::default();
+    let on_focusin = {
+        let last_focused = last_focused.clone();
+        Closure::<dyn FnMut(web_sys::Event)>::new(move |ev: web_sys::Event| {
+            let target = ev.target().and_then(|t| t.dyn_into::<Element>().ok());
+            *last_focused.borrow_mut() = target;
+        })
+    };
+    let watched = swal.clone();
+    let on_mutation = Closure::<dyn FnMut(Array, MutationObserver)>::new(move |_, _| {
+        // An alert below another modal alert is inert
+        // and must not take the focus back.
+        if !watched.is_connected() || watched.closest("[inert]").ok().flatten().is_some() {
+            return;
+        }
+        let was_removed = last_focused
+            .borrow()
+            .as_ref()
+            .is_some_and(|focused| !focused.is_connected());
+        let focus_lost =
+            active_element_of(&watched).is_none_or(|active| !watched.contains(Some(&active)));
+        if was_removed && focus_lost {
+            *last_focused.borrow_mut() = None;
+            focus_first(&watched);
+        }
+    });
+    let Ok(observer) = MutationObserver::new(on_mutation.as_ref().unchecked_ref()) else {
+        return;
+    };
+    let init = MutationObserverInit::new();
+    init.set_child_list(true);
+    init.set_subtree(true);
+    if observer.observe_with_options(swal, &init).is_err() {
+        return;
+    }
+    let _ = swal.add_event_listener_with_callback("focusin", on_focusin.as_ref().unchecked_ref());
+    FOCUS_WATCHERS.with(|w| {
+        w.borrow_mut().push(FocusWatcher {
+            swal: swal.clone(),
+            observer,
+            _on_mutation: on_mutation,
+            on_focusin,
+        })
+    });
+}
+
+/// Stops watching the content of the Swal.
+pub(crate) fn unwatch_focus(swal: &Element) {
+    FOCUS_WATCHERS.with(|w| {
+        w.borrow_mut().retain(|watcher| {
+            if watcher.swal.is_same_node(Some(swal)) {
+                watcher.observer.disconnect();
+                let _ = swal.remove_event_listener_with_callback(
+                    "focusin",
+                    watcher.on_focusin.as_ref().unchecked_ref(),
+                );
+                false
+            } else {
+                true
+            }
+        })
+    });
+}
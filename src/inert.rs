@@ -0,0 +1,157 @@
+use std::cell::RefCell;
+
+use leptos::*;
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::Element;
+
+use crate::registry;
+
+thread_local! {
+    /// The elements made inert by the Swals that are currently opened.
+    static INERT_STACK: RefCell<InertStack<Element>> = const { RefCell::new(InertStack::new()) };
+}
+
+/// The previous state of the elements that were made inert,
+/// for each Swal that is currently opened. A new entry is
+/// pushed every time a modal Swal opens, so that nested or
+/// queued alerts restore exactly what they changed.
+///
+/// It doesn't touch the DOM: `E` is the type of the elements.
+pub(crate) struct InertStack<E> {
+    entries: Vec<InertEntry<E>>,
+}
+
+/// The elements made inert by one Swal.
+struct InertEntry<E> {
+    /// The root of the Swal (the backdrop).
+    swal: E,
+
+    /// The elements that were made inert because of this Swal.
+    siblings: Vec<InertState<E>>,
+}
+
+/// The state of an element before it was made inert.
+pub(crate) struct InertState<E> {
+    pub(crate) element: E,
+
+    /// The previous value of the "inert" attribute.
+    pub(crate) inert: Option<String>,
+
+    /// The previous value of the "aria-hidden" attribute.
+    pub(crate) aria_hidden: Option<String>,
+}
+
+impl<E> InertStack<E> {
+    pub(crate) const fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Keeps the previous state of the elements made inert by the given Swal.
+    pub(crate) fn push(&mut self, swal: E, siblings: Vec<InertState<E>>) {
+        self.entries.push(InertEntry { swal, siblings });
+    }
+
+    /// Forgets the elements made inert by the given Swal, and returns
+    /// the states to restore, in the order in which they must be restored.
+    /// `same` checks if two elements are the same node.
+    ///
+    /// It returns nothing if this Swal didn't make anything inert.
+    pub(crate) fn remove(&mut self, swal: &E, same: impl Fn(&E, &E) -> bool) -> Vec<InertState<E>> {
+        let Some(index) = self.entries.iter().rposition(|entry| same(&entry.swal, swal)) else {
+            return Vec::new();
+        };
+        let mut entry = self.entries.remove(index);
+        // When an alert that isn't the topmost one closes, the elements
+        // that the alerts above it made inert must remain inert. They
+        // are restored to their original state when these alerts close.
+        entry.siblings.retain(|state| {
+            let above = self.entries[index..].iter_mut().find_map(|above| {
+                above
+                    .siblings
+                    .iter_mut()
+                    .find(|s| same(&s.element, &state.element))
+            });
+            match above {
+                Some(above) => {
+                    above.inert.clone_from(&state.inert);
+                    above.aria_hidden.clone_from(&state.aria_hidden);
+                    false
+                }
+                None => true,
+            }
+        });
+        // In reverse order, in case an element appears twice.
+        entry.siblings.reverse();
+        entry.siblings
+    }
+}
+
+/// Sets "inert" and "aria-hidden" on all the siblings of the Swal,
+/// so that neither screen readers, nor the pointer, nor a call to `focus()`
+/// can reach the content behind the popup.
+///
+/// When the Swal isn't a direct child of `boundary` (see the "target" option),
+/// the siblings of each of its ancestors become inert too, up to `boundary`,
+/// even across a shadow root.
+pub(crate) fn make_siblings_inert(swal: &Element, boundary: &Element) {
+    let mut siblings = Vec::new();
+    let mut node = swal.clone();
+    loop {
+        let (children, parent) = if let Some(parent) = node.parent_element() {
+            (parent.children(), parent)
+        } else if let Some(shadow_root) = node
+            .parent_node()
+            .and_then(|parent| parent.dyn_into::<web_sys::ShadowRoot>().ok())
+        {
+            // The top of a shadow tree: its siblings are the
+            // children of the shadow root, and the search
+            // continues from the host.
+            (shadow_root.children(), shadow_root.host())
+        } else {
+            break;
+        };
+        for i in 0..children.length() {
+            let Some(element) = children.item(i) else {
+                continue;
+            };
+            // The alerts below this one are made inert by `set_stacked`,
+            // and they may close before this one.
+            if element.is_same_node(Some(&node)) || registry::is_alert(&element) {
+                continue;
+            }
+            siblings.push(InertState {
+                inert: element.get_attribute("inert"),
+                aria_hidden: element.get_attribute("aria-hidden"),
+                element,
+            });
+        }
+        if parent.is_same_node(Some(boundary)) {
+            break;
+        }
+        node = parent;
+    }
+    for state in siblings.iter() {
+        let _ = state.element.set_attribute("inert", "");
+        let _ = state.element.set_attribute("aria-hidden", "true");
+    }
+    INERT_STACK.with_borrow_mut(|stack| stack.push(swal.clone(), siblings));
+}
+
+/// Restores the "inert" and "aria-hidden" attributes of the elements
+/// that were made inert when the given Swal opened.
+///
+/// Does nothing if this Swal didn't make anything inert.
+pub(crate) fn restore_siblings(swal: &Element) {
+    let states = INERT_STACK.with_borrow_mut(|stack| stack.remove(swal, |a, b| a.is_same_node(Some(b))));
+    for state in states {
+        restore_attribute(&state.element, "inert", &state.inert);
+        restore_attribute(&state.element, "aria-hidden", &state.aria_hidden);
+    }
+}
+
+fn restore_attribute(element: &Element, name: &str, value: &Option<String>) {
+    let _ = match value {
+        Some(value) => element.set_attribute(name, value),
+        None => element.remove_attribute(name),
+    };
+}
@@ -0,0 +1,132 @@
+/// A keyboard shortcut, such as `Ctrl+Enter` or `D`
+/// (see the "key_bindings" option of [`SwalOptions`](crate::SwalOptions)).
+///
+/// The key is compared to the `key` property of the keyboard event
+/// (`"d"`, `"Enter"`, `"ArrowUp"`, etc.), regardless of the case.
+/// The modifiers must be exactly the same: `D` doesn't match `Ctrl+D`.
+///
+/// A shortcut without Ctrl, Alt or Meta is ignored while the user
+/// is typing in a field of the popup.
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// let combo = KeyCombo::from("Ctrl+Shift+D");
+/// assert_eq!(combo, KeyCombo::new("D").ctrl().shift());
+/// assert_eq!(combo.to_string(), "Ctrl+Shift+D");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    /// The key, as given by the `key` property of the event.
+    pub key: String,
+
+    /// Whether or not the Control key must be pressed.
+    pub ctrl: bool,
+
+    /// Whether or not the Alt key (Option on macOS) must be pressed.
+    pub alt: bool,
+
+    /// Whether or not the Shift key must be pressed.
+    pub shift: bool,
+
+    /// Whether or not the Meta key (Command on macOS) must be pressed.
+    pub meta: bool,
+}
+
+impl KeyCombo {
+    /// Creates a shortcut with the given key and no modifiers.
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// The Control key must be pressed too.
+    pub fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    /// The Alt key must be pressed too.
+    pub fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    /// The Shift key must be pressed too.
+    pub fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    /// The Meta key must be pressed too.
+    pub fn meta(mut self) -> Self {
+        self.meta = true;
+        self
+    }
+
+    /// Checks if the given keyboard event matches this shortcut.
+    pub fn matches(&self, ev: &web_sys::KeyboardEvent) -> bool {
+        self.matches_key(
+            &ev.key(),
+            [ev.ctrl_key(), ev.alt_key(), ev.shift_key(), ev.meta_key()],
+        )
+    }
+
+    /// Checks if the given key and modifiers (Ctrl, Alt, Shift and Meta) match this shortcut.
+    pub(crate) fn matches_key(&self, key: &str, modifiers: [bool; 4]) -> bool {
+        key.eq_ignore_ascii_case(&self.key)
+            && modifiers == [self.ctrl, self.alt, self.shift, self.meta]
+    }
+
+    /// Whether or not the shortcut could be typed in a field
+    /// (it has no Ctrl, Alt or Meta modifier).
+    pub(crate) fn is_typed(&self) -> bool {
+        !self.ctrl && !self.alt && !self.meta
+    }
+}
+
+/// Parses a shortcut such as `"Ctrl+Shift+D"`. The modifiers
+/// (`Ctrl`, `Alt`, `Shift` and `Meta`, regardless of the case)
+/// come first, and the last part is the key. `"+"` alone is the plus key.
+impl From<&str> for KeyCombo {
+    fn from(value: &str) -> Self {
+        let value = value.trim();
+        let (modifiers, key) = match value.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None if value == "+" => ("", "+"),
+            None => value.rsplit_once('+').unwrap_or(("", value)),
+        };
+        let mut combo = KeyCombo::new(key.trim());
+        for modifier in modifiers.split('+').map(str::trim) {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => combo.ctrl = true,
+                "alt" | "option" => combo.alt = true,
+                "shift" => combo.shift = true,
+                "meta" | "cmd" | "command" => combo.meta = true,
+                _ => {}
+            }
+        }
+        combo
+    }
+}
+
+impl std::fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let modifiers = [
+            (self.ctrl, "Ctrl+"),
+            (self.alt, "Alt+"),
+            (self.shift, "Shift+"),
+            (self.meta, "Meta+"),
+        ];
+        for (pressed, name) in modifiers {
+            if pressed {
+                f.write_str(name)?;
+            }
+        }
+        f.write_str(&self.key)
+    }
+}
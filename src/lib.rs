@@ -4,18 +4,59 @@
 //! Many features have been implemented, but not all of them. To learn more about how to use this
 //! create, then please read the documentation provided on [GitHub](https://github.com/ThomasGysemans/leptos_sweetalert).
 
+mod focus;
+mod inert;
+mod key_combo;
+mod registry;
+mod scroll_lock;
+mod swal_action;
+mod swal_animation;
+mod swal_animation_class;
+mod swal_backdrop;
+mod swal_body;
+mod swal_context;
 mod swal_dismiss_reason;
+mod swal_error;
+mod swal_grow;
+mod swal_handle;
 mod swal_icon;
+mod swal_isolation;
 mod swal_options;
+mod swal_position;
 mod swal_result;
+mod swal_state;
+mod swal_target;
+mod swal_value;
+mod transition;
 
 #[allow(non_snake_case)]
 pub mod Swal;
 
+pub use key_combo::KeyCombo;
+pub use swal_action::SwalAction;
+pub use swal_animation::SwalAnimation;
+pub use swal_animation_class::SwalAnimationClass;
+pub use swal_animation_class::SwalAnimationPreset;
+pub use swal_backdrop::SwalBackdrop;
+pub use swal_body::SwalBody;
+pub use swal_context::SwalContext;
 pub use swal_dismiss_reason::SwalDismissReason;
+pub use swal_error::SwalError;
+pub use swal_grow::SwalGrow;
+pub use swal_handle::SwalHandle;
+pub use swal_handle::SwalResultFuture;
+pub use swal_handle::SwalUpdate;
 pub use swal_icon::SwalIcon;
 pub use swal_icon::SwalIconLike;
+pub use swal_isolation::SwalIsolation;
+pub use swal_isolation::SwalStylesheet;
+pub use swal_options::SwalFuture;
 pub use swal_options::SwalOptions;
+pub use swal_position::SwalPosition;
+pub use swal_result::SwalOutcome;
 pub use swal_result::SwalResult;
+pub use swal_state::SwalState;
+pub use swal_target::SwalTarget;
+pub use swal_value::SwalValue;
 
 mod tests;
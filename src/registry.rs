@@ -0,0 +1,345 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use leptos::leptos_dom::helpers::{TimeoutHandle, WindowListenerHandle};
+use leptos::Disposer;
+use web_sys::Element;
+
+use crate::swal_isolation::mount_point;
+use crate::swal_result::AnyResult;
+use crate::{KeyCombo, SwalAction, SwalState};
+
+thread_local! {
+    /// The alerts that are in the DOM, from the bottom
+    /// of the stack to the topmost one. An alert leaves
+    /// the registry once it's removed from the DOM.
+    static REGISTRY: RefCell<Vec<SwalInstance>> = const { RefCell::new(Vec::new()) };
+
+    /// The number used to generate the ID of the next alert.
+    static NEXT_ID: Cell<u64> = const { Cell::new(1) };
+}
+
+/// Gives the result of an alert to its "then" callback and to its handle.
+/// The type of the value is erased since it depends on the alert.
+pub(crate) type Resolver = Box<dyn FnOnce(Option<AnyResult>)>;
+
+/// A lifecycle hook of an alert and the popup it receives.
+pub(crate) type HookCall = (fn(&Element), Rc<Element>);
+
+/// A copy of the lifecycle hooks of an alert (see [`SwalOptions`](crate::SwalOptions)).
+#[derive(Clone, Copy)]
+pub(crate) struct SwalHooks {
+    pub(crate) will_open: fn(&Element),
+    pub(crate) did_render: fn(&Element),
+    pub(crate) did_open: fn(&Element),
+    pub(crate) will_close: fn(&Element),
+    pub(crate) did_close: fn(&Element),
+    pub(crate) did_destroy: fn(&Element),
+}
+
+/// A lifecycle hook of an alert, in the order in which they're called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum SwalHook {
+    WillOpen,
+    DidRender,
+    DidOpen,
+    WillClose,
+    DidClose,
+    DidDestroy,
+}
+
+impl SwalHooks {
+    /// Gets the function of the given hook.
+    pub(crate) fn get(&self, hook: SwalHook) -> fn(&Element) {
+        match hook {
+            SwalHook::WillOpen => self.will_open,
+            SwalHook::DidRender => self.did_render,
+            SwalHook::DidOpen => self.did_open,
+            SwalHook::WillClose => self.will_close,
+            SwalHook::DidClose => self.did_close,
+            SwalHook::DidDestroy => self.did_destroy,
+        }
+    }
+}
+
+/// The state of an alert that is in the DOM.
+pub(crate) struct SwalInstance {
+    /// The generated ID of the root of the alert (see [`next_id`]).
+    pub(crate) id: String,
+
+    /// Where the alert is in its lifecycle.
+    /// It must be changed with [`SwalInstance::transition`].
+    state: SwalState,
+
+    /// The root of the alert (the backdrop).
+    /// It's `None` until the alert is added to the DOM.
+    pub(crate) root: Option<Element>,
+
+    /// Gives the result of the alert to its "then" callback and to its handle.
+    /// The point of this is to be able to execute the callback when the alert
+    /// gets closed by the Escape key or by clicking on the backdrop.
+    /// It's `None` once the alert starts closing.
+    pub(crate) resolver: Option<Resolver>,
+
+    /// The "auto_close" parameter of the options.
+    pub(crate) auto_close: bool,
+
+    /// The "allow_enter_key" parameter of the options.
+    pub(crate) allow_enter_key: bool,
+
+    /// The "key_bindings" parameter of the options.
+    pub(crate) key_bindings: Vec<(KeyCombo, SwalAction)>,
+
+    /// The "stop_escape_propagation" parameter of the options.
+    pub(crate) stop_escape_propagation: bool,
+
+    /// The lifecycle hooks of the alert.
+    pub(crate) hooks: Option<SwalHooks>,
+
+    /// The popup (the `.swal-container` element), given to the lifecycle hooks.
+    pub(crate) popup: Option<Rc<Element>>,
+
+    /// The last lifecycle hook that was called (see [`SwalInstance::next_hook`]).
+    last_hook: Option<SwalHook>,
+
+    /// The timeout making the alert go from [`SwalState::Opening`] to [`SwalState::Open`].
+    pub(crate) opening: Option<TimeoutHandle>,
+
+    /// The timer of the alert (see the "timer" option).
+    pub(crate) timer: Option<TimeoutHandle>,
+
+    /// The listeners closing the alert when the user navigates
+    /// through the history (see the "close_on_route_change" option).
+    pub(crate) route_listeners: Vec<WindowListenerHandle>,
+
+    /// The element that had the focus before opening the alert.
+    pub(crate) previously_focused: Option<web_sys::HtmlElement>,
+
+    /// Whether or not the alert needs the scroll of the page to be locked.
+    pub(crate) locks_scroll: bool,
+
+    /// The element in which the alert was added, if it's scoped
+    /// to it (see the "scoped_to_target" option).
+    pub(crate) scoped_target: Option<Element>,
+
+    /// The ID of the alert below this one, if it was opened
+    /// with [`Swal::push`](crate::Swal::push).
+    pub(crate) parent: Option<String>,
+
+    /// Disposes the reactive owner of the alert, in which its view was created
+    /// (signals, effects, event listeners, etc.). It's dropped once the alert
+    /// is removed from the DOM.
+    pub(crate) owner: Option<Disposer>,
+}
+
+impl SwalInstance {
+    pub(crate) fn new(id: String, resolver: Resolver) -> Self {
+        Self {
+            id,
+            state: SwalState::Opening,
+            root: None,
+            resolver: Some(resolver),
+            auto_close: true,
+            allow_enter_key: true,
+            key_bindings: Vec::new(),
+            stop_escape_propagation: true,
+            hooks: None,
+            popup: None,
+            last_hook: None,
+            opening: None,
+            timer: None,
+            route_listeners: Vec::new(),
+            previously_focused: None,
+            locks_scroll: false,
+            scoped_target: None,
+            parent: None,
+            owner: None,
+        }
+    }
+
+    /// Makes the alert go to the given state, if it's allowed
+    /// (see [`SwalState::can_become`]). The timeouts and listeners
+    /// that don't apply to the new state are canceled.
+    /// It returns `false` if the transition isn't allowed.
+    pub(crate) fn transition(&mut self, next: SwalState) -> bool {
+        if !self.state.can_become(next) {
+            return false;
+        }
+        self.state = next;
+        if next == SwalState::Open {
+            // It already fired.
+            self.opening = None;
+        }
+        if next == SwalState::Closing {
+            if let Some(opening) = self.opening.take() {
+                opening.clear();
+            }
+            if let Some(timer) = self.timer.take() {
+                timer.clear();
+            }
+            for listener in self.route_listeners.drain(..) {
+                listener.remove();
+            }
+        }
+        true
+    }
+
+    /// Gets the given lifecycle hook and the popup it receives, unless this hook
+    /// or a later one was already called. This way, each hook is called at most
+    /// once and in order, even if an alert is closed while it's opening.
+    pub(crate) fn next_hook(&mut self, hook: SwalHook) -> Option<HookCall> {
+        let (hooks, popup) = (self.hooks?, self.popup.clone()?);
+        if self.last_hook.is_some_and(|last| last >= hook) {
+            return None;
+        }
+        self.last_hook = Some(hook);
+        Some((hooks.get(hook), popup))
+    }
+}
+
+/// Generates a unique ID for the root of a new alert,
+/// such as `"swal-1"`. The title gets this ID followed by `"-title"`.
+pub(crate) fn next_id() -> String {
+    let n = NEXT_ID.with(|n| n.replace(n.get() + 1));
+    format!("swal-{}", n)
+}
+
+/// Adds an alert on top of the stack.
+pub(crate) fn register(instance: SwalInstance) {
+    REGISTRY.with(|r| r.borrow_mut().push(instance));
+}
+
+/// Removes an alert from the registry, once it's removed from the DOM.
+/// It returns `None` if the alert isn't in the registry.
+pub(crate) fn unregister(id: &str) -> Option<SwalInstance> {
+    REGISTRY.with(|r| {
+        let mut registry = r.borrow_mut();
+        let index = registry.iter().position(|instance| instance.id == id)?;
+        Some(registry.remove(index))
+    })
+}
+
+/// Checks if there is no alert in the DOM anymore.
+pub(crate) fn is_empty() -> bool {
+    REGISTRY.with_borrow(|r| r.is_empty())
+}
+
+/// Modifies the state of an alert, whatever its lifecycle state.
+/// It returns `None` if the alert isn't in the registry.
+pub(crate) fn with_instance<R>(id: &str, f: impl FnOnce(&mut SwalInstance) -> R) -> Option<R> {
+    REGISTRY.with(|r| {
+        r.borrow_mut()
+            .iter_mut()
+            .find(|instance| instance.id == id)
+            .map(f)
+    })
+}
+
+/// Gets the alert at the top of the stack,
+/// ignoring the alerts that started closing.
+fn with_topmost<R>(f: impl FnOnce(&SwalInstance) -> Option<R>) -> Option<R> {
+    REGISTRY.with_borrow(|r| {
+        r.iter()
+            .rev()
+            .find(|instance| instance.state.is_active())
+            .and_then(f)
+    })
+}
+
+/// Gets the ID of the alert at the top of the stack.
+pub(crate) fn topmost() -> Option<String> {
+    with_topmost(|instance| Some(instance.id.clone()))
+}
+
+/// Gets the root of the alert at the top of the stack,
+/// if it was added to the DOM.
+pub(crate) fn topmost_root() -> Option<Element> {
+    with_topmost(|instance| instance.root.clone())
+}
+
+/// Gets the ID of the alert below the topmost one,
+/// if the topmost one was opened with [`Swal::push`](crate::Swal::push).
+pub(crate) fn topmost_parent() -> Option<String> {
+    with_topmost(|instance| instance.parent.clone())
+}
+
+/// Checks if an alert was opened above the given one
+/// with [`Swal::push`](crate::Swal::push).
+pub(crate) fn has_child(id: &str) -> bool {
+    REGISTRY.with_borrow(|r| {
+        r.iter().any(|instance| {
+            instance.state.is_active() && instance.parent.as_deref() == Some(id)
+        })
+    })
+}
+
+/// Gets the lifecycle state of the given alert.
+/// It's [`SwalState::Closed`] once the alert left the registry.
+pub(crate) fn state_of(id: &str) -> SwalState {
+    with_instance(id, |instance| instance.state).unwrap_or(SwalState::Closed)
+}
+
+/// Checks if the given alert is opening or opened.
+pub(crate) fn is_active(id: &str) -> bool {
+    state_of(id).is_active()
+}
+
+/// Checks if one of the opened alerts locks the scroll of the page.
+pub(crate) fn any_locks_scroll() -> bool {
+    REGISTRY.with_borrow(|r| {
+        r.iter()
+            .any(|instance| instance.state.is_active() && instance.locks_scroll)
+    })
+}
+
+/// Checks if an alert other than the given one is scoped to the given target,
+/// including the alerts that are closing since they're still in the DOM.
+pub(crate) fn is_target_used(target: &Element, except: &str) -> bool {
+    REGISTRY.with_borrow(|r| {
+        r.iter().any(|instance| {
+            instance.id != except
+                && instance
+                    .scoped_target
+                    .as_ref()
+                    .is_some_and(|t| t.is_same_node(Some(target)))
+        })
+    })
+}
+
+/// Checks if the given element was added to the DOM for one of the alerts:
+/// its root, or the host of its shadow root if it's isolated.
+pub(crate) fn is_alert(element: &Element) -> bool {
+    REGISTRY.with_borrow(|r| {
+        r.iter().any(|instance| {
+            instance
+                .root
+                .as_ref()
+                .is_some_and(|root| mount_point(root).is_same_node(Some(element)))
+        })
+    })
+}
+
+/// Checks if the given element is within an alert that started closing,
+/// and that is about to be removed from the DOM.
+pub(crate) fn is_in_closing_alert(element: &Element) -> bool {
+    REGISTRY.with_borrow(|r| {
+        r.iter().any(|instance| {
+            instance.state == SwalState::Closing
+                && instance
+                    .root
+                    .as_ref()
+                    .is_some_and(|root| root.contains(Some(element)))
+        })
+    })
+}
+
+/// Gets the root of the alert that most recently started closing.
+pub(crate) fn last_closing() -> Option<Element> {
+    REGISTRY.with_borrow(|r| {
+        r.iter()
+            .rev()
+            .find(|instance| instance.state == SwalState::Closing)
+            .and_then(|instance| instance.root.clone())
+    })
+}
+
@@ -0,0 +1,145 @@
+use std::cell::RefCell;
+
+use leptos::*;
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::window;
+
+use crate::swal_error::{try_body, try_document};
+use crate::SwalError;
+
+thread_local! {
+    /// The state of the page before it was locked.
+    /// It's `None` when the page isn't locked.
+    static SCROLL_LOCK: RefCell<Option<ScrollLockState>> = const { RefCell::new(None) };
+}
+
+/// What must be restored when the page gets unlocked.
+struct ScrollLockState {
+    /// The inline styles of `<html>` (the "style" attribute).
+    html_style: Option<String>,
+
+    /// The inline styles of `<body>` (the "style" attribute).
+    body_style: Option<String>,
+
+    /// The horizontal scroll position of the page.
+    scroll_x: f64,
+
+    /// The vertical scroll position of the page.
+    scroll_y: f64,
+}
+
+/// Prevents the page behind the Swal from scrolling.
+///
+/// The body is fixed at its current scroll position, which is
+/// the only reliable solution on mobile devices. The previous inline
+/// styles are stored so that [`unlock_scroll`] can restore them exactly.
+///
+/// Does nothing if the page is already locked.
+pub(crate) fn lock_scroll(scrollbar_padding: bool, height_auto: bool) -> Result<(), SwalError> {
+    if is_scroll_locked() {
+        return Ok(());
+    }
+
+    let window = window().ok_or(SwalError::NoDocument)?;
+    let html = try_document()?
+        .document_element()
+        .and_then(|html| html.dyn_into::<web_sys::HtmlElement>().ok())
+        .ok_or(SwalError::NoBody)?;
+    let body = try_body()?;
+
+    let scroll_x = window.scroll_x().unwrap_or(0.0);
+    let scroll_y = window.scroll_y().unwrap_or(0.0);
+
+    // The width of the scrollbar is the difference between
+    // the width of the window and the width of the document.
+    // It must be measured before hiding the overflow.
+    let scrollbar_width = window
+        .inner_width()
+        .ok()
+        .and_then(|w| w.as_f64())
+        .map(|w| w - html.client_width() as f64)
+        .unwrap_or(0.0);
+
+    let body_padding_right = window
+        .get_computed_style(&body)
+        .ok()
+        .flatten()
+        .and_then(|style| style.get_property_value("padding-right").ok())
+        .map(|value| parse_px(&value))
+        .unwrap_or(0.0);
+
+    SCROLL_LOCK.with(|s| {
+        *s.borrow_mut() = Some(ScrollLockState {
+            html_style: html.get_attribute("style"),
+            body_style: body.get_attribute("style"),
+            scroll_x,
+            scroll_y,
+        })
+    });
+
+    let html_style = html.style();
+    let body_style = body.style();
+    html_style.set_property("overflow", "hidden")?;
+    body_style.set_property("overflow", "hidden")?;
+    body_style.set_property("position", "fixed")?;
+    body_style.set_property("top", &format!("{}px", -scroll_y))?;
+    body_style.set_property("left", &format!("{}px", -scroll_x))?;
+    body_style.set_property("right", "0")?;
+    if height_auto {
+        html_style.set_property("height", "auto")?;
+        body_style.set_property("height", "auto")?;
+    }
+    if scrollbar_padding && scrollbar_width > 0.0 {
+        // Avoids the layout shift caused by the disappearance of the scrollbar.
+        body_style.set_property(
+            "padding-right",
+            &format!("{}px", body_padding_right + scrollbar_width),
+        )?;
+    }
+    Ok(())
+}
+
+/// Restores the inline styles of `<html>` and `<body>`
+/// as well as the scroll position they had before [`lock_scroll`].
+///
+/// Does nothing if the page isn't locked.
+/// If there is no document anymore, the page is considered unlocked.
+pub(crate) fn unlock_scroll() -> Result<(), SwalError> {
+    let Some(state) = SCROLL_LOCK.with(|s| s.borrow_mut().take()) else {
+        return Ok(());
+    };
+
+    let document = try_document()?;
+    if let Some(html) = document.document_element() {
+        restore_style_attribute(&html, state.html_style);
+    }
+    if let Some(body) = document.body() {
+        restore_style_attribute(&body, state.body_style);
+    }
+    if let Some(window) = window() {
+        window.scroll_to_with_x_and_y(state.scroll_x, state.scroll_y);
+    }
+    Ok(())
+}
+
+/// Checks if the page is currently locked.
+pub(crate) fn is_scroll_locked() -> bool {
+    SCROLL_LOCK.with_borrow(|s| s.is_some())
+}
+
+fn restore_style_attribute(element: &web_sys::Element, style: Option<String>) {
+    let _ = match style {
+        Some(style) => element.set_attribute("style", &style),
+        None => element.remove_attribute("style"),
+    };
+}
+
+/// Parses a CSS length in pixels (such as `"12.5px"`).
+/// Any other value is considered to be `0`.
+pub(crate) fn parse_px(value: &str) -> f64 {
+    value
+        .trim()
+        .strip_suffix("px")
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .unwrap_or(0.0)
+}
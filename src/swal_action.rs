@@ -0,0 +1,44 @@
+/// What a keyboard shortcut does (see the "key_bindings" option of
+/// [`SwalOptions`](crate::SwalOptions)).
+///
+/// Each action clicks the corresponding button, exactly as if the user
+/// did it. It does nothing if the button isn't displayed or if it's disabled.
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// let opts = SwalOptions::<&str> {
+///     title: "Delete the file?",
+///     show_deny_button: true,
+///     key_bindings: vec![(KeyCombo::from("d"), SwalAction::Deny)],
+///     ..SwalOptions::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwalAction {
+    /// Clicks the confirmation button.
+    Confirm,
+
+    /// Clicks the deny button.
+    Deny,
+
+    /// Clicks the cancel button.
+    Cancel,
+
+    /// Clicks the close button (see the "show_close_button" option).
+    Close,
+}
+
+impl SwalAction {
+    /// The CSS selector of the button clicked by this action.
+    pub(crate) fn button_selector(self) -> &'static str {
+        match self {
+            SwalAction::Confirm => ".swal-confirm-button",
+            SwalAction::Deny => ".swal-deny-button",
+            SwalAction::Cancel => ".swal-cancel-button",
+            SwalAction::Close => ".swal-close-button",
+        }
+    }
+}
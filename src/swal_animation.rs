@@ -0,0 +1,58 @@
+use leptos::*;
+use web_sys::window;
+
+/// Whether or not the popup should be animated.
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// let opts = SwalOptions::<&str> {
+///     title: "No animation at all",
+///     animation: SwalAnimation::Off,
+///     ..SwalOptions::default()
+/// };
+/// assert_eq!(SwalOptions::<&str>::default().animation, SwalAnimation::Auto);
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum SwalAnimation {
+    /// The popup is always animated.
+    On,
+
+    /// The popup is never animated.
+    Off,
+
+    /// The popup is animated, unless the user asked
+    /// the system to minimize the amount of animations
+    /// (`prefers-reduced-motion: reduce`).
+    /// It is the default value.
+    #[default]
+    Auto,
+}
+
+impl SwalAnimation {
+    /// Whether or not the popup should be animated,
+    /// according to the preferences of the user.
+    pub fn is_enabled(&self) -> bool {
+        self.resolve(prefers_reduced_motion())
+    }
+
+    /// Whether or not the popup should be animated,
+    /// knowing if the user prefers reduced motion.
+    pub(crate) fn resolve(&self, prefers_reduced_motion: bool) -> bool {
+        match self {
+            SwalAnimation::On => true,
+            SwalAnimation::Off => false,
+            SwalAnimation::Auto => !prefers_reduced_motion,
+        }
+    }
+}
+
+/// Checks if the user asked the system to minimize
+/// the amount of non-essential motion.
+fn prefers_reduced_motion() -> bool {
+    window()
+        .and_then(|w| w.match_media("(prefers-reduced-motion: reduce)").ok().flatten())
+        .is_some_and(|query| query.matches())
+}
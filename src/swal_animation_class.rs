@@ -0,0 +1,108 @@
+/// The animations provided by the stylesheets,
+/// to be used with the `show_class` and `hide_class` options.
+///
+/// Each preset has a class for the opening of the popup
+/// (`.swal-show-fade`, `.swal-show-zoom`, etc.) and another
+/// for its closing (`.swal-hide-fade`, `.swal-hide-zoom`, etc.).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SwalAnimationPreset {
+    /// The popup fades in and out.
+    Fade,
+
+    /// The popup grows when opening and shrinks when closing.
+    Zoom,
+
+    /// The popup comes from the bottom and leaves through the top.
+    SlideUp,
+
+    /// The popup comes from the top and leaves through the bottom.
+    SlideDown,
+
+    /// The popup bounces when opening and when closing.
+    Bounce,
+
+    /// The popup shakes horizontally.
+    Shake,
+}
+
+impl SwalAnimationPreset {
+    /// The CSS class animating the opening of the popup.
+    pub fn show_class(&self) -> &'static str {
+        match self {
+            SwalAnimationPreset::Fade => "swal-show-fade",
+            SwalAnimationPreset::Zoom => "swal-show-zoom",
+            SwalAnimationPreset::SlideUp => "swal-show-slide-up",
+            SwalAnimationPreset::SlideDown => "swal-show-slide-down",
+            SwalAnimationPreset::Bounce => "swal-show-bounce",
+            SwalAnimationPreset::Shake => "swal-show-shake",
+        }
+    }
+
+    /// The CSS class animating the closing of the popup.
+    pub fn hide_class(&self) -> &'static str {
+        match self {
+            SwalAnimationPreset::Fade => "swal-hide-fade",
+            SwalAnimationPreset::Zoom => "swal-hide-zoom",
+            SwalAnimationPreset::SlideUp => "swal-hide-slide-up",
+            SwalAnimationPreset::SlideDown => "swal-hide-slide-down",
+            SwalAnimationPreset::Bounce => "swal-hide-bounce",
+            SwalAnimationPreset::Shake => "swal-hide-shake",
+        }
+    }
+}
+
+/// The classes added to the popup (the `.swal-container` element)
+/// when it opens (`show_class`) or when it closes (`hide_class`).
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// let opts = SwalOptions::<&str> {
+///     title: "Hello",
+///     show_class: SwalAnimationClass::Preset(SwalAnimationPreset::Zoom),
+///     // The classes of an external library, such as Animate.css:
+///     hide_class: SwalAnimationClass::Custom(String::from("animate__animated animate__fadeOutUp")),
+///     ..SwalOptions::default()
+/// };
+/// assert_eq!(opts.show_class.show_classes(), "swal-show-zoom");
+/// assert_eq!(opts.hide_class.hide_classes(), "animate__animated animate__fadeOutUp");
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub enum SwalAnimationClass {
+    /// No class is added, only the default
+    /// transition of the backdrop is used.
+    /// It is the default value.
+    #[default]
+    Default,
+
+    /// One of the animations provided by the stylesheets.
+    Preset(SwalAnimationPreset),
+
+    /// Any class (or classes separated by spaces),
+    /// for example the ones of an animation library.
+    /// Note that the closing of the popup waits for the
+    /// animation of these classes to end.
+    Custom(String),
+}
+
+impl SwalAnimationClass {
+    /// The classes to add to the popup when it opens.
+    pub fn show_classes(&self) -> &str {
+        match self {
+            SwalAnimationClass::Default => "",
+            SwalAnimationClass::Preset(preset) => preset.show_class(),
+            SwalAnimationClass::Custom(classes) => classes,
+        }
+    }
+
+    /// The classes to add to the popup when it closes.
+    pub fn hide_classes(&self) -> &str {
+        match self {
+            SwalAnimationClass::Default => "",
+            SwalAnimationClass::Preset(preset) => preset.hide_class(),
+            SwalAnimationClass::Custom(classes) => classes,
+        }
+    }
+}
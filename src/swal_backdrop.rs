@@ -0,0 +1,38 @@
+/// Defines what is displayed behind the popup.
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// let opts = SwalOptions::<&str> {
+///     title: "Hello",
+///     backdrop: SwalBackdrop::Custom(String::from("rgba(0, 0, 123, 0.4)")),
+///     ..SwalOptions::default()
+/// };
+/// assert!(opts.backdrop.is_modal());
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub enum SwalBackdrop {
+    /// The backdrop defined by the stylesheets.
+    /// It is the default value.
+    #[default]
+    Default,
+
+    /// No backdrop at all.
+    /// The page behind the popup isn't dimmed and remains
+    /// clickable, which makes the popup a non-modal dialog.
+    None,
+
+    /// A custom value for the CSS "background" property
+    /// of the backdrop (a color, a gradient, an image, etc.).
+    Custom(String),
+}
+
+impl SwalBackdrop {
+    /// Whether or not the popup should be treated as a modal dialog.
+    /// Only `SwalBackdrop::None` makes it non-modal.
+    pub fn is_modal(&self) -> bool {
+        self != &SwalBackdrop::None
+    }
+}
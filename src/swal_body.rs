@@ -0,0 +1,59 @@
+use leptos::*;
+
+/// A custom view added to the popup (see the "body" option of [`SwalOptions`](crate::SwalOptions)).
+///
+/// It wraps a [`ViewFn`], which is only called when the popup is opened,
+/// within the reactive owner of the popup. The view can therefore get
+/// the [`SwalContext`](crate::SwalContext) of the popup with `use_context`.
+///
+/// # Example
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_sweetalert::*;
+///
+/// let opts = SwalOptions::<&str> {
+///     title: "Hello",
+///     body: SwalBody::from(|| view! { <input /> }),
+///     ..SwalOptions::default()
+/// };
+/// ```
+#[derive(Default, Clone)]
+pub struct SwalBody(ViewFn);
+
+impl SwalBody {
+    /// Creates the view.
+    pub fn run(&self) -> View {
+        self.0.run()
+    }
+}
+
+impl std::fmt::Debug for SwalBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SwalBody(..)")
+    }
+}
+
+impl From<ViewFn> for SwalBody {
+    fn from(view_fn: ViewFn) -> Self {
+        Self(view_fn)
+    }
+}
+
+impl<F, IV> From<F> for SwalBody
+where
+    F: Fn() -> IV + 'static,
+    IV: IntoView,
+{
+    fn from(f: F) -> Self {
+        Self(ViewFn::from(f))
+    }
+}
+
+/// A view that was already created. Unlike a function,
+/// it isn't created within the reactive owner of the popup.
+impl From<View> for SwalBody {
+    fn from(view: View) -> Self {
+        Self(ViewFn::from(move || view.clone()))
+    }
+}
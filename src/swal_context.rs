@@ -0,0 +1,107 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use leptos::html::Div;
+use leptos::*;
+
+use crate::{Swal, SwalResult, SwalValue};
+
+/// Allows the custom view of a popup (see the "body" option of
+/// [`SwalOptions`](crate::SwalOptions)) to drive this popup.
+///
+/// It's provided as a context to the view. Its generic parameter (`T`)
+/// must be the type of the value of the popup (see [`SwalValue`]).
+///
+/// # Example
+///
+/// ```no_run
+/// # use leptos::*;
+/// # use leptos_sweetalert::*;
+///
+/// #[component]
+/// fn NameInput() -> impl IntoView {
+///     let swal = expect_context::<SwalContext<String>>();
+///     swal.set_confirm_enabled(false);
+///     let on_input = move |ev| {
+///         let name = event_target_value(&ev);
+///         swal.set_confirm_enabled(!name.is_empty());
+///         swal.set_value(name);
+///     };
+///     view! { <input on:input=on_input /> }
+/// }
+///
+/// Swal::fire(SwalOptions::<&str, SwalIcon, String> {
+///     title: "What is your name?",
+///     body: SwalBody::from(|| view! { <NameInput /> }),
+///     ..SwalOptions::typed()
+/// });
+/// ```
+#[derive(Clone)]
+pub struct SwalContext<T = bool> {
+    /// The ID of the popup.
+    id: String,
+
+    /// The value given by [`SwalContext::set_value`].
+    value: Rc<RefCell<Option<T>>>,
+
+    /// Whether or not the confirmation button can be clicked.
+    confirm_enabled: RwSignal<bool>,
+
+    /// The popup (the `.swal-container` element).
+    container: NodeRef<Div>,
+}
+
+impl<T: SwalValue> SwalContext<T> {
+    pub(crate) fn new(id: String, container: NodeRef<Div>) -> Self {
+        Self {
+            id,
+            value: Rc::new(RefCell::new(None)),
+            confirm_enabled: create_rw_signal(true),
+            container,
+        }
+    }
+
+    /// Closes the popup, with the same behavior as [`Swal::close`],
+    /// even if it's not the topmost one.
+    /// It returns `false` if the popup is not opened anymore.
+    pub fn close(&self, result: Option<SwalResult<T>>) -> bool {
+        Swal::close_id(&self.id, result.map(SwalResult::into_any))
+    }
+
+    /// Sets the value given to the "then" callback when the
+    /// confirmation button is clicked, instead of the value
+    /// created from the input of the popup.
+    pub fn set_value(&self, value: T) {
+        *self.value.borrow_mut() = Some(value);
+    }
+
+    /// Enables or disables the confirmation button.
+    pub fn set_confirm_enabled(&self, enabled: bool) {
+        self.confirm_enabled.set(enabled);
+    }
+
+    /// Displays a message below the body of the popup
+    /// (see [`Swal::show_validation_message`]).
+    pub fn show_validation_message(&self, message: &str) {
+        if let Some(container) = self.container.get_untracked() {
+            Swal::set_validation_message(&container, Some(message));
+        }
+    }
+
+    /// Hides the message displayed by [`SwalContext::show_validation_message`].
+    pub fn reset_validation_message(&self) {
+        if let Some(container) = self.container.get_untracked() {
+            Swal::set_validation_message(&container, None);
+        }
+    }
+
+    /// Gets the value given by [`SwalContext::set_value`].
+    pub(crate) fn value(&self) -> Option<T> {
+        self.value.borrow().clone()
+    }
+
+    /// Whether or not the confirmation button can be clicked.
+    pub(crate) fn is_confirm_enabled(&self) -> Signal<bool> {
+        self.confirm_enabled.into()
+    }
+}
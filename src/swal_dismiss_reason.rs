@@ -1,5 +1,19 @@
 /// The reasons why an alert has been closed.
-#[derive(Debug, PartialEq)]
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// assert_eq!(SwalDismissReason::Esc.to_string(), "esc");
+/// assert_eq!(SwalDismissReason::RouteChange.to_string(), "route-change");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub enum SwalDismissReason {
     /// The user clicked the backdrop.
     Backdrop,
@@ -7,16 +21,41 @@ pub enum SwalDismissReason {
     /// The user clicked the cancel button.
     Cancel,
 
-    /// The user clicked the close button.
-    ///
-    /// This member is actually not used by
-    /// this crate, but it is provided for
-    /// you if you decide to manually close
-    /// the alert for some reason, and need
-    /// a way to detect that you did that
-    /// programmatically.
+    /// The user clicked the close button
+    /// (see the "show_close_button" option).
     Close,
 
     /// The user clicked the Escape key.
     Esc,
+
+    /// The timer ran out (see the "timer" option).
+    Timer,
+
+    /// The alert was dismissed by the code,
+    /// with [`Swal::dismiss`](crate::Swal::dismiss) or [`SwalHandle::dismiss`](crate::SwalHandle::dismiss).
+    Programmatic,
+
+    /// Another alert was opened with [`Swal::fire`](crate::Swal::fire)
+    /// while this one was still opened.
+    Replaced,
+
+    /// The user navigated through the history of the browser
+    /// (see the "close_on_route_change" option).
+    RouteChange,
+}
+
+impl std::fmt::Display for SwalDismissReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SwalDismissReason::Backdrop => "backdrop",
+            SwalDismissReason::Cancel => "cancel",
+            SwalDismissReason::Close => "close",
+            SwalDismissReason::Esc => "esc",
+            SwalDismissReason::Timer => "timer",
+            SwalDismissReason::Programmatic => "programmatic",
+            SwalDismissReason::Replaced => "replaced",
+            SwalDismissReason::RouteChange => "route-change",
+        };
+        write!(f, "{}", name)
+    }
 }
@@ -0,0 +1,112 @@
+use leptos::*;
+use web_sys::wasm_bindgen::JsValue;
+use web_sys::{Document, HtmlElement};
+
+/// The errors that can occur when opening or closing an alert,
+/// returned by [`Swal::try_fire`](crate::Swal::try_fire),
+/// [`Swal::try_close`](crate::Swal::try_close), etc.
+///
+/// The functions that don't start with `try_` log these errors
+/// in the console instead of returning them.
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// let error = SwalError::InvalidCss {
+///     property: String::from("width"),
+///     value: String::from("wide"),
+/// };
+/// assert_eq!(error.to_string(), "invalid value for the CSS property \"width\": \"wide\"");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum SwalError {
+    /// There is no window or no document,
+    /// for example when the code doesn't run in a browser.
+    NoDocument,
+
+    /// The document has no `<body>` (or no `<html>`).
+    NoBody,
+
+    /// No element matches the selector given as target
+    /// (see [`SwalTarget::Selector`](crate::SwalTarget::Selector)).
+    TargetNotFound(String),
+
+    /// A DOM operation failed with the given JavaScript error.
+    DomError(JsValue),
+
+    /// One of the CSS options (`width`, `padding`, `color` or `background`)
+    /// isn't a valid value for its CSS property.
+    InvalidCss {
+        /// The CSS property.
+        property: String,
+
+        /// The invalid value given in the options.
+        value: String,
+    },
+
+    /// The popup is isolated in a shadow root without any stylesheet
+    /// (see [`SwalIsolation::ShadowDom`](crate::SwalIsolation::ShadowDom)),
+    /// so it would have no styles at all.
+    NoStylesheet,
+}
+
+impl std::fmt::Display for SwalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SwalError::NoDocument => write!(f, "could not find the document"),
+            SwalError::NoBody => write!(f, "could not find the body of the document"),
+            SwalError::TargetNotFound(selector) => {
+                write!(f, "no element matches the target \"{}\"", selector)
+            }
+            SwalError::DomError(error) => write!(f, "DOM error: {:?}", error),
+            SwalError::InvalidCss { property, value } => write!(
+                f,
+                "invalid value for the CSS property \"{}\": \"{}\"",
+                property, value
+            ),
+            SwalError::NoStylesheet => write!(f, "the isolated popup has no stylesheet"),
+        }
+    }
+}
+
+impl std::error::Error for SwalError {}
+
+impl From<JsValue> for SwalError {
+    fn from(error: JsValue) -> Self {
+        SwalError::DomError(error)
+    }
+}
+
+/// Gets the document without panicking
+/// (unlike [`leptos::document`]).
+pub(crate) fn try_document() -> Result<Document, SwalError> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or(SwalError::NoDocument)
+}
+
+/// Gets the body of the document.
+pub(crate) fn try_body() -> Result<HtmlElement, SwalError> {
+    try_document()?.body().ok_or(SwalError::NoBody)
+}
+
+/// Checks if the given value is valid for the given CSS property.
+/// An empty value means that the stylesheets are used, so it's valid.
+pub(crate) fn check_css(property: &str, value: &str) -> Result<(), SwalError> {
+    if value.is_empty() || web_sys::css::supports_with_value(property, value).unwrap_or(true) {
+        Ok(())
+    } else {
+        Err(SwalError::InvalidCss {
+            property: property.to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
+/// Logs an error instead of returning it,
+/// for the functions that don't start with `try_`.
+pub(crate) fn log_error(context: &str, error: &SwalError) {
+    logging::error!("{}: {}", context, error);
+}
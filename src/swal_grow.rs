@@ -0,0 +1,48 @@
+/// Makes the popup grow to fill the screen
+/// in one or both directions.
+///
+/// The value is applied through a modifier class
+/// on the container (`.swal-grow-row`, `.swal-grow-column`
+/// or `.swal-grow-fullscreen`).
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// let opts = SwalOptions::<&str> {
+///     title: "Full width banner",
+///     position: SwalPosition::Top,
+///     grow: SwalGrow::Row,
+///     ..SwalOptions::default()
+/// };
+/// assert_eq!(opts.grow.to_string(), "row");
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum SwalGrow {
+    /// The popup keeps its regular size.
+    /// It is the default value.
+    #[default]
+    None,
+
+    /// The popup takes the full width of the screen.
+    Row,
+
+    /// The popup takes the full height of the screen.
+    Column,
+
+    /// The popup fills the whole viewport.
+    Fullscreen,
+}
+
+impl std::fmt::Display for SwalGrow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SwalGrow::None => "none",
+            SwalGrow::Row => "row",
+            SwalGrow::Column => "column",
+            SwalGrow::Fullscreen => "fullscreen",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,316 @@
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::task::{Context, Poll, Waker};
+
+use leptos::leptos_dom::helpers::TimeoutHandle;
+use leptos::*;
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::Element;
+
+use crate::registry;
+use crate::Swal;
+use crate::SwalDismissReason;
+use crate::SwalResult;
+use crate::SwalState;
+use crate::SwalValue;
+
+/// A handle to the alert created by [`Swal::fire`].
+///
+/// Unlike the functions of the [`Swal`] module, which act on
+/// whatever alert is currently displayed, all the methods of the
+/// handle are scoped to the alert that was created by the call to `fire`.
+/// Once this alert is closed, or if it got replaced by another one,
+/// the methods do nothing.
+///
+/// The handle can be cloned, all the clones refer to the same alert.
+/// Its generic parameter (`T`) is the type of the value of the alert
+/// (see [`SwalValue`]).
+///
+/// # Example
+///
+/// ```no_run
+/// # use leptos_sweetalert::*;
+///
+/// let handle = Swal::fire(SwalOptions::<&str>::basic("Loading..."));
+/// // ...later, even if another alert was opened in the meantime:
+/// handle.close(Some(SwalResult::confirmed()));
+/// ```
+#[derive(Clone)]
+pub struct SwalHandle<T = bool> {
+    state: Rc<SwalHandleState<T>>,
+}
+
+struct SwalHandleState<T> {
+    /// The root of the alert (the backdrop).
+    /// It's `None` until the alert is actually opened
+    /// (it waits for the previous alert to close).
+    root: RefCell<Option<Element>>,
+
+    /// The timeout replacing the previous alert before opening this one,
+    /// canceled if this alert is closed in the meantime.
+    pending: Cell<Option<TimeoutHandle>>,
+
+    /// Whether or not the alert was closed,
+    /// or canceled before it could be opened.
+    closed: Cell<bool>,
+
+    /// The result given when the alert was closed.
+    result: RefCell<Option<SwalResult<T>>>,
+
+    /// The tasks waiting for the result, one per pending
+    /// [`SwalResultFuture`] (see [`WakerSlot`]).
+    wakers: RefCell<Vec<Weak<WakerSlot>>>,
+}
+
+/// The waker of the task awaiting a [`SwalResultFuture`].
+/// It's replaced when the future is polled by another task.
+type WakerSlot = RefCell<Option<Waker>>;
+
+/// The new texts of an alert, for [`SwalHandle::update`].
+/// The fields set to `None` remain unchanged.
+///
+/// # Example
+///
+/// ```no_run
+/// # use leptos_sweetalert::*;
+///
+/// let handle = Swal::fire(SwalOptions::<&str>::basic("Uploading..."));
+/// handle.update(SwalUpdate {
+///     title: Some(String::from("Almost done")),
+///     text: Some(String::from("50%")),
+///     ..SwalUpdate::default()
+/// });
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SwalUpdate {
+    /// The new title.
+    pub title: Option<String>,
+
+    /// The new text (the description).
+    /// An empty string hides it.
+    pub text: Option<String>,
+
+    /// The new label of the confirmation button.
+    pub confirm_button_text: Option<String>,
+
+    /// The new label of the deny button.
+    pub deny_button_text: Option<String>,
+
+    /// The new label of the cancel button.
+    pub cancel_button_text: Option<String>,
+}
+
+impl<T: SwalValue> SwalHandle<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Rc::new(SwalHandleState {
+                root: RefCell::new(None),
+                pending: Cell::new(None),
+                closed: Cell::new(false),
+                result: RefCell::new(None),
+                wakers: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Sets the root of the alert once it was added to the DOM.
+    pub(crate) fn set_root(&self, root: Element) {
+        self.state.pending.take();
+        *self.state.root.borrow_mut() = Some(root);
+    }
+
+    /// Sets the timeout that will open the alert.
+    pub(crate) fn set_pending(&self, pending: TimeoutHandle) {
+        self.state.pending.set(Some(pending));
+    }
+
+    /// Marks the alert as closed and wakes up
+    /// the tasks waiting for its result.
+    pub(crate) fn resolve(&self, result: Option<SwalResult<T>>) {
+        if self.state.closed.replace(true) {
+            return;
+        }
+        *self.state.result.borrow_mut() = result;
+        for slot in self.state.wakers.take() {
+            if let Some(waker) = slot.upgrade().and_then(|slot| slot.take()) {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Whether or not the alert was closed,
+    /// or canceled before it could be opened.
+    pub fn is_closed(&self) -> bool {
+        self.state.closed.get()
+    }
+
+    /// Gets where the alert is in its lifecycle.
+    pub fn state(&self) -> SwalState {
+        match self.root() {
+            Some(root) => registry::state_of(&root.id()),
+            None if self.is_closed() => SwalState::Closed,
+            None => SwalState::Opening,
+        }
+    }
+
+    /// Checks if the alert is currently displayed.
+    /// It returns `false` before the alert is actually opened
+    /// (if it waits for a previous alert to close),
+    /// and as soon as it starts closing.
+    pub fn is_open(&self) -> bool {
+        !self.is_closed() && self.root().is_some_and(|root| root.is_connected())
+    }
+
+    /// Gets the root of the alert (the backdrop),
+    /// or `None` if it isn't opened yet.
+    pub fn root(&self) -> Option<Element> {
+        self.state.root.borrow().clone()
+    }
+
+    /// Closes this alert, with the same behavior as [`Swal::close`].
+    /// It returns `false` if this alert is not opened anymore.
+    ///
+    /// If the alert is waiting for a previous alert to close,
+    /// then it gets canceled and will never be opened.
+    pub fn close(&self, result: Option<SwalResult<T>>) -> bool {
+        if self.is_closed() {
+            return false;
+        }
+        if self.root().is_none() {
+            if let Some(pending) = self.state.pending.take() {
+                pending.clear();
+            }
+            self.resolve(None);
+            return true;
+        }
+        match self.root() {
+            Some(root) if self.is_open() => {
+                Swal::close_id(&root.id(), result.map(SwalResult::into_any))
+            }
+            _ => false,
+        }
+    }
+
+    /// Dismisses this alert with [`SwalDismissReason::Programmatic`](crate::SwalDismissReason::Programmatic).
+    /// It returns `false` if this alert is not opened anymore.
+    pub fn dismiss(&self) -> bool {
+        self.close(Some(SwalResult::canceled(SwalDismissReason::Programmatic)))
+    }
+
+    /// Changes the texts of this alert.
+    /// Does nothing if the alert is not opened.
+    pub fn update(&self, update: SwalUpdate) {
+        if !self.is_open() {
+            return;
+        }
+        if let Some(title) = update.title {
+            if let Some(el) = self.query(".swal-title") {
+                el.set_text_content(Some(&title));
+            }
+        }
+        if let Some(text) = update.text {
+            if let Some(el) = self.query(".swal-text") {
+                el.set_text_content(Some(&text));
+                if text.is_empty() {
+                    let _ = el.set_attribute("hidden", "");
+                } else {
+                    let _ = el.remove_attribute("hidden");
+                }
+            }
+        }
+        let buttons = [
+            (update.confirm_button_text, ".swal-confirm-button"),
+            (update.deny_button_text, ".swal-deny-button"),
+            (update.cancel_button_text, ".swal-cancel-button"),
+        ];
+        for (text, selector) in buttons {
+            if let (Some(text), Some(button)) = (text, self.query(selector)) {
+                button.set_text_content(Some(&text));
+            }
+        }
+    }
+
+    /// Waits for this alert to be closed.
+    ///
+    /// The future resolves with the result given when closing the alert,
+    /// or `None` if it was closed without any result (see [`Swal::close`]).
+    pub fn result(&self) -> SwalResultFuture<T> {
+        SwalResultFuture {
+            handle: self.clone(),
+            waker: Rc::default(),
+        }
+    }
+
+    /// Gets the confirmation button of this alert.
+    pub fn confirm_button(&self) -> Option<web_sys::HtmlButtonElement> {
+        self.query_as(".swal-confirm-button")
+    }
+
+    /// Gets the deny button of this alert.
+    pub fn deny_button(&self) -> Option<web_sys::HtmlButtonElement> {
+        self.query_as(".swal-deny-button")
+    }
+
+    /// Gets the cancel button of this alert.
+    pub fn cancel_button(&self) -> Option<web_sys::HtmlButtonElement> {
+        self.query_as(".swal-cancel-button")
+    }
+
+    /// Gets the popup of this alert (the `.swal-container` element).
+    pub fn popup(&self) -> Option<Element> {
+        self.query(".swal-container")
+    }
+
+    /// Finds an element in this alert, as long as it's opened.
+    fn query(&self, selector: &str) -> Option<Element> {
+        if !self.is_open() {
+            return None;
+        }
+        self.root()?.query_selector(selector).ok().flatten()
+    }
+
+    fn query_as<E: JsCast>(&self, selector: &str) -> Option<E> {
+        self.query(selector)?.dyn_into::<E>().ok()
+    }
+}
+
+impl<T: SwalValue> std::fmt::Debug for SwalHandle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwalHandle")
+            .field("is_open", &self.is_open())
+            .field("is_closed", &self.is_closed())
+            .finish()
+    }
+}
+
+/// The future returned by [`SwalHandle::result`].
+pub struct SwalResultFuture<T = bool> {
+    handle: SwalHandle<T>,
+    waker: Rc<WakerSlot>,
+}
+
+impl<T: SwalValue> Future for SwalResultFuture<T> {
+    type Output = Option<SwalResult<T>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let state = &self.handle.state;
+        if state.closed.get() {
+            return Poll::Ready(state.result.borrow().clone());
+        }
+        let mut waker = self.waker.borrow_mut();
+        match waker.as_ref() {
+            Some(current) if current.will_wake(cx.waker()) => {}
+            Some(_) => *waker = Some(cx.waker().clone()),
+            None => {
+                *waker = Some(cx.waker().clone());
+                // The futures that were dropped don't wait anymore.
+                let mut wakers = state.wakers.borrow_mut();
+                wakers.retain(|slot| slot.strong_count() > 0);
+                wakers.push(Rc::downgrade(&self.waker));
+            }
+        }
+        Poll::Pending
+    }
+}
@@ -132,9 +132,7 @@ impl SwalIcon {
               </g>
             </svg>
         })
-        .into_view()
-        .into_html_element()
-        .expect("Could not create Success Icon")
+        .into_any()
     }
 
     fn warning_icon() -> HtmlElement<AnyElement> {
@@ -146,9 +144,7 @@ impl SwalIcon {
                 </svg>
             </div>
         })
-        .into_view()
-        .into_html_element()
-        .expect("Could not create Warning Icon")
+        .into_any()
     }
 
     fn error_icon() -> HtmlElement<AnyElement> {
@@ -160,9 +156,7 @@ impl SwalIcon {
                 </svg>
             </div>
         })
-        .into_view()
-        .into_html_element()
-        .expect("Could not create Warning Icon")
+        .into_any()
     }
 
     fn info_icon() -> HtmlElement<AnyElement> {
@@ -173,9 +167,7 @@ impl SwalIcon {
                 </svg>
             </div>
         })
-        .into_view()
-        .into_html_element()
-        .expect("Could not create Warning Icon")
+        .into_any()
     }
 
     fn question_icon() -> HtmlElement<AnyElement> {
@@ -187,17 +179,12 @@ impl SwalIcon {
                 </svg>
             </div>
         })
-        .into_view()
-        .into_html_element()
-        .expect("Could not create Warning Icon")
+        .into_any()
     }
 
     /// Displays nothing when no icon is needed.
     pub fn none_icon() -> HtmlElement<AnyElement> {
-        (view! { <div /> })
-            .into_view()
-            .into_html_element()
-            .expect("Could not display empty icon")
+        (view! { <div /> }).into_any()
     }
 }
 
@@ -208,6 +195,7 @@ impl std::fmt::Display for SwalIcon {
 }
 
 impl SwalIconLike for SwalIcon {
+    #[allow(clippy::match_ref_pats)]
     fn get_icon_element(&self) -> HtmlElement<AnyElement> {
         match self {
             &SwalIcon::SUCCESS => SwalIcon::success_icon(),
@@ -0,0 +1,136 @@
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::{Document, Element, ShadowRoot, ShadowRootInit, ShadowRootMode};
+
+use crate::swal_error::try_document;
+use crate::SwalError;
+
+/// Protects the popup from the styles of the page.
+///
+/// Global rules of the page (a `button {}` rule, a CSS reset, etc.) also apply
+/// to the popup. With `SwalIsolation::ShadowDom`, the popup is built inside the
+/// shadow root of an element added to the target, so only the given stylesheets
+/// apply to it. They must include the core styles of the crate, and the theme if any.
+/// An empty list is rejected with [`SwalError::NoStylesheet`].
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// let opts = SwalOptions::<&str> {
+///     title: "Isolated",
+///     isolation: SwalIsolation::ShadowDom(vec![
+///         // The stylesheets generated by Trunk from the SCSS files of the crate.
+///         SwalStylesheet::Copy(String::from("link[href*='sweetalert']")),
+///     ]),
+///     ..SwalOptions::default()
+/// };
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum SwalIsolation {
+    /// The popup is added directly to the target,
+    /// and the styles of the page apply to it.
+    #[default]
+    None,
+
+    /// The popup is added to a shadow root
+    /// with only the given stylesheets.
+    ShadowDom(Vec<SwalStylesheet>),
+}
+
+/// A stylesheet to add to the shadow root of an isolated popup
+/// (see [`SwalIsolation::ShadowDom`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SwalStylesheet {
+    /// The given CSS, added in a `<style>` element.
+    Css(String),
+
+    /// The stylesheet at the given URL, added with a `<link>` element.
+    Url(String),
+
+    /// A copy of the `<style>` and `<link>` elements of the document
+    /// that match the given CSS selector.
+    Copy(String),
+}
+
+/// The class of the element holding the shadow root of an isolated popup.
+const SHADOW_HOST_CLASS: &str = "swal-shadow-host";
+
+impl SwalIsolation {
+    /// Checks that an isolated popup has stylesheets,
+    /// since the ones of the page don't apply to it.
+    pub(crate) fn check(&self) -> Result<(), SwalError> {
+        match self {
+            SwalIsolation::ShadowDom(stylesheets) if stylesheets.is_empty() => {
+                Err(SwalError::NoStylesheet)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Adds the Swal to the target, in a shadow root if it must be isolated.
+    pub(crate) fn mount(&self, target: &Element, swal: &Element) -> Result<(), SwalError> {
+        match self {
+            SwalIsolation::None => {
+                target.append_child(swal)?;
+            }
+            SwalIsolation::ShadowDom(stylesheets) => {
+                let document = try_document()?;
+                let host = document.create_element("div")?;
+                host.set_class_name(SHADOW_HOST_CLASS);
+                let shadow_root = host.attach_shadow(&ShadowRootInit::new(ShadowRootMode::Open))?;
+                for stylesheet in stylesheets {
+                    for element in stylesheet.to_elements(&document)? {
+                        shadow_root.append_child(&element)?;
+                    }
+                }
+                shadow_root.append_child(swal)?;
+                target.append_child(&host)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SwalStylesheet {
+    /// Creates the elements adding this stylesheet to a shadow root.
+    fn to_elements(&self, document: &Document) -> Result<Vec<Element>, SwalError> {
+        match self {
+            SwalStylesheet::Css(css) => {
+                let style = document.create_element("style")?;
+                style.set_text_content(Some(css));
+                Ok(vec![style])
+            }
+            SwalStylesheet::Url(url) => {
+                let link = document.create_element("link")?;
+                link.set_attribute("rel", "stylesheet")?;
+                link.set_attribute("href", url)?;
+                Ok(vec![link])
+            }
+            SwalStylesheet::Copy(selector) => {
+                let found = document.query_selector_all(selector)?;
+                let mut elements = Vec::new();
+                for i in 0..found.length() {
+                    if let Some(element) = found
+                        .item(i)
+                        .and_then(|node| node.clone_node_with_deep(true).ok())
+                        .and_then(|node| node.dyn_into::<Element>().ok())
+                    {
+                        elements.push(element);
+                    }
+                }
+                Ok(elements)
+            }
+        }
+    }
+}
+
+/// Gets the element that was added to the target for the given Swal:
+/// the host of its shadow root if it's isolated, or the Swal itself.
+pub(crate) fn mount_point(swal: &Element) -> Element {
+    swal.parent_node()
+        .and_then(|parent| parent.dyn_into::<ShadowRoot>().ok())
+        .map(|shadow_root| shadow_root.host())
+        .filter(|host| host.class_list().contains(SHADOW_HOST_CLASS))
+        .unwrap_or_else(|| swal.clone())
+}
@@ -1,12 +1,35 @@
+use crate::swal_error::check_css;
+use crate::KeyCombo;
+use crate::SwalAction;
+use crate::SwalAnimation;
+use crate::SwalAnimationClass;
+use crate::SwalBackdrop;
+use crate::SwalBody;
+use crate::SwalError;
+use crate::SwalGrow;
 use crate::SwalIcon;
 use crate::SwalIconLike;
+use crate::SwalIsolation;
+use crate::SwalPosition;
 use crate::SwalResult;
+use crate::SwalTarget;
+use crate::SwalValue;
 use leptos::*;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use web_sys::Element;
+
+/// The future returned by the `async_pre_confirm` option.
+/// It gives the value of the alert, or `None` to keep it opened.
+pub type SwalFuture<T> = Pin<Box<dyn Future<Output = Option<T>>>>;
 
 /// Defines the parameters of a Sweet Alert.
 /// It uses generic parameters to simplify the use
 /// of the crate. The first one (`S`) defines the type for all
 /// text fields and the second one (`I`) defines the type for the icon.
+/// The third one (`T`) is the type of the value given when
+/// the alert is confirmed (see [`SwalValue`]).
 ///
 /// # Example
 ///
@@ -31,12 +54,21 @@ use leptos::*;
 ///     title: "This is a title",
 ///     ..SwalOptions::default()
 /// };
+///
+/// // `default()` is only available for alerts giving a simple `bool`.
+/// // Use `typed()` for the alerts giving another type of value:
+/// let opt = SwalOptions::<&str, SwalIcon, String> {
+///     title: "What is your name?",
+///     then: |result: SwalResult<String>| {},
+///     ..SwalOptions::typed()
+/// };
 /// ```
 #[derive(Debug, Clone)]
-pub struct SwalOptions<S = &'static str, I = SwalIcon>
+pub struct SwalOptions<S = &'static str, I = SwalIcon, T = bool>
 where
     S: AsRef<str> + Clone + Default + leptos::IntoView,
     I: SwalIconLike + Default + Clone + Copy,
+    T: SwalValue,
 {
     /// The title of the alert.
     /// If its value is an empty string,
@@ -77,16 +109,69 @@ where
     /// Defaults to "Deny".
     pub deny_button_text: S,
 
+    /// A text to display below the buttons.
+    /// If its value is an empty string,
+    /// no footer will be displayed.
+    pub footer: S,
+
+    /// Should a close button (a cross in the top right corner) be displayed?
+    /// Clicking it dismisses the alert with [`SwalDismissReason::Close`](crate::SwalDismissReason::Close).
+    /// It defaults to `false`.
+    pub show_close_button: bool,
+
     /// Function to execute before confirming.
     pub pre_confirm: fn(),
 
+    /// Asynchronous function to execute before confirming,
+    /// after `pre_confirm`. It gives the value of the alert,
+    /// or `None` to keep the alert opened (for example after
+    /// calling [`Swal::show_validation_message`](crate::Swal::show_validation_message)).
+    /// A loader is displayed in the meantime.
+    ///
+    /// When it's not defined, the value is created
+    /// from the input of the popup (see [`SwalValue`]).
+    ///
+    /// Note that the alert isn't closed if `auto_close` is `false`.
+    pub async_pre_confirm: Option<fn() -> SwalFuture<T>>,
+
     /// Function to execute before denying.
     pub pre_deny: fn(),
 
     /// Function to execute when an alert ends.
     /// It will not get called if no reason was given to the
     /// `Swal::close()` method (which allows you to close the popup programmatically).
-    pub then: fn(SwalResult),
+    pub then: fn(SwalResult<T>),
+
+    /// Function to execute once the popup was built,
+    /// right before it gets added to the DOM.
+    /// It receives the popup (the `.swal-container` element).
+    pub will_open: fn(&Element),
+
+    /// Function to execute once the popup was added to the DOM,
+    /// before the opening animation starts.
+    /// It receives the popup (the `.swal-container` element).
+    pub did_render: fn(&Element),
+
+    /// Function to execute once the opening animation has started
+    /// and the focus was given to the popup. Use it to focus
+    /// a custom field of the `body`, for example.
+    /// It receives the popup (the `.swal-container` element).
+    pub did_open: fn(&Element),
+
+    /// Function to execute when the popup starts closing,
+    /// before the closing animation.
+    /// It receives the popup (the `.swal-container` element).
+    pub will_close: fn(&Element),
+
+    /// Function to execute once the closing animation is over
+    /// and the popup was removed from the DOM.
+    /// It receives the popup (the `.swal-container` element).
+    pub did_close: fn(&Element),
+
+    /// Function to execute after `did_close`,
+    /// once everything related to the popup was cleaned up.
+    /// It receives the popup (the `.swal-container` element).
+    pub did_destroy: fn(&Element),
 
     /// Should the alert close itself when a button is pressed
     /// and when it is dismissed?
@@ -97,16 +182,148 @@ where
     /// accessibility concerns.
     pub auto_close: bool,
 
+    /// Closes the alert automatically after the given duration,
+    /// with [`SwalDismissReason::Timer`](crate::SwalDismissReason::Timer).
+    /// The timer starts once the alert is opened.
+    /// Defaults to `None` (no timer).
+    pub timer: Option<Duration>,
+
+    /// Should a progress bar showing the remaining time of the timer be displayed
+    /// at the bottom of the popup? It's only displayed if there is a timer.
+    /// Defaults to `false`.
+    pub timer_progress_bar: bool,
+
+    /// Should the alert be dismissed with
+    /// [`SwalDismissReason::RouteChange`](crate::SwalDismissReason::RouteChange)
+    /// when the user navigates through the history of the browser
+    /// (the "popstate" and "hashchange" events)?
+    /// It's ignored if `auto_close` is `false`.
+    ///
+    /// Note that "hashchange" is also triggered by the links
+    /// to an anchor of the page, including the ones of the alert.
+    /// Defaults to `false`.
+    pub close_on_route_change: bool,
+
+    /// Should the Enter key confirm the alert (see [`SwalAction::Confirm`])?
+    /// It's ignored when the focus is on a button, a link, a textarea
+    /// or an editable element, which handle the key themselves.
+    /// Defaults to `true`.
+    pub allow_enter_key: bool,
+
+    /// Custom keyboard shortcuts of the alert, such as "D" to deny it.
+    /// They are checked before the default ones (Escape, Enter, etc.),
+    /// and the first matching shortcut is used.
+    /// Defaults to an empty list.
+    pub key_bindings: Vec<(KeyCombo, SwalAction)>,
+
+    /// Should the Escape key stop propagating once the alert handled it?
+    /// It prevents the Escape handler of the page from being triggered too,
+    /// for example one that closes a panel below the alert.
+    /// The other keys always reach the rest of the page.
+    ///
+    /// Note that the event handlers of Leptos (`on:keydown`) are delegated
+    /// to the window, so the handlers of the body that need the Escape key
+    /// must be added with `on:keydown:undelegated`.
+    /// Defaults to `true`.
+    pub stop_escape_propagation: bool,
+
     /// Should animate the popup?
-    /// A value of `false` will stop all animations,
+    /// A value of `SwalAnimation::Off` will stop all animations,
     /// including the opening and closing transitions
     /// as well as the icon animations.
+    /// Defaults to `SwalAnimation::Auto`, which stops all animations
+    /// only if the user prefers reduced motion.
+    pub animation: SwalAnimation,
+
+    /// The classes animating the opening of the popup.
+    /// Use a [`SwalAnimationPreset`](crate::SwalAnimationPreset)
+    /// or the classes of your own animation.
+    /// Defaults to `SwalAnimationClass::Default`.
+    pub show_class: SwalAnimationClass,
+
+    /// The classes animating the closing of the popup.
+    /// The popup is removed from the DOM once this animation is over.
+    /// Defaults to `SwalAnimationClass::Default`.
+    pub hide_class: SwalAnimationClass,
+
+    /// Where the popup should be displayed on the screen.
+    /// Defaults to `SwalPosition::Center`.
+    pub position: SwalPosition,
+
+    /// Makes the popup fill the screen horizontally,
+    /// vertically or entirely.
+    /// Defaults to `SwalGrow::None`.
+    pub grow: SwalGrow,
+
+    /// The width of the popup, as any valid CSS value
+    /// (`"400px"`, `"80%"`, etc.).
+    /// It replaces the minimum and maximum widths of the stylesheets,
+    /// but the popup never gets wider than its backdrop.
+    /// If its value is an empty string,
+    /// the width defined by the stylesheets is used.
+    pub width: S,
+
+    /// The padding of the popup, as any valid CSS value.
+    /// If its value is an empty string,
+    /// the padding defined by the stylesheets is used.
+    pub padding: S,
+
+    /// The color of the title and of the text.
+    /// If its value is an empty string,
+    /// the color defined by the stylesheets is used.
+    pub color: S,
+
+    /// The background of the popup, as any valid
+    /// value for the CSS "background" property.
+    /// If its value is an empty string,
+    /// the background defined by the stylesheets is used.
+    pub background: S,
+
+    /// What to display behind the popup.
+    /// Defaults to `SwalBackdrop::Default`.
+    ///
+    /// Note that `SwalBackdrop::None` makes the popup a non-modal dialog.
+    pub backdrop: SwalBackdrop,
+
+    /// Should padding be added to the body to compensate for
+    /// the width of the scrollbar while the page is locked?
+    /// It avoids a layout shift when the popup opens.
+    /// Defaults to `true`.
+    pub scrollbar_padding: bool,
+
+    /// Should the height of `<html>` and `<body>` be set to `auto`
+    /// while the page is locked? Some layouts set it to `100%`,
+    /// which makes the page jump back to the top when it's locked.
+    ///
+    /// Set it to `false` if your layout relies on the height of these elements.
     /// Defaults to `true`.
-    pub animation: bool,
+    ///
+    /// Note that the page behind the popup is locked (it cannot scroll)
+    /// as long as the popup is opened, unless there is no backdrop
+    /// (see [`SwalBackdrop::None`]).
+    pub height_auto: bool,
+
+    /// The element in which the popup is added.
+    /// Defaults to `SwalTarget::Body`.
+    pub target: SwalTarget,
+
+    /// Should the backdrop cover only the target instead of the whole viewport?
+    /// In that case, only the content of the target becomes inert, the page
+    /// isn't locked and the focus isn't held within the popup.
+    /// It's ignored if the target is the body.
+    /// Defaults to `false`.
+    pub scoped_to_target: bool,
+
+    /// Protects the popup from the styles of the page
+    /// by building it inside a shadow root.
+    /// Defaults to `SwalIsolation::None`.
+    pub isolation: SwalIsolation,
 
     /// A custom view to be added into the generated HTML of the popup.
     /// This view is inserted below the description and above the buttons.
-    pub body: View,
+    /// It's created when the popup opens, and it can drive the popup
+    /// via the [`SwalContext`](crate::SwalContext) given as a context.
+    pub body: SwalBody,
 }
 
 impl<S, I> Default for SwalOptions<S, I>
@@ -115,6 +332,31 @@ where
     I: SwalIconLike + Default + Clone + Copy,
 {
     fn default() -> Self {
+        Self::typed()
+    }
+}
+
+impl<S, I, T> SwalOptions<S, I, T>
+where
+    S: AsRef<str> + Clone + Default + leptos::IntoView,
+    I: SwalIconLike + Default + Clone + Copy,
+    T: SwalValue,
+{
+    /// Creates the default options of an alert giving a value of type `T`
+    /// when it's confirmed. It's the same as `default()`, which only
+    /// exists for the alerts giving a `bool` so that the type of the value
+    /// doesn't have to be specified for simple alerts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use leptos_sweetalert::*;
+    ///
+    /// let opts = SwalOptions::<&str, SwalIcon, String>::typed();
+    /// assert_eq!(opts.title, "");
+    /// assert!(opts.async_pre_confirm.is_none());
+    /// ```
+    pub fn typed() -> Self {
         Self {
             title: S::default(),
             text: S::default(),
@@ -125,14 +367,94 @@ where
             confirm_button_text: S::default(), // "Ok" is added maually
             cancel_button_text: S::default(),  // "Cancel" is added manually
             deny_button_text: S::default(),    // "Deny" is added manually
+            footer: S::default(),
+            show_close_button: false,
             pre_confirm: || {},
+            async_pre_confirm: None,
             pre_deny: || {},
             then: |_| {},
+            will_open: |_| {},
+            did_render: |_| {},
+            did_open: |_| {},
+            will_close: |_| {},
+            did_close: |_| {},
+            did_destroy: |_| {},
             auto_close: true,
-            animation: true,
-            body: View::default(),
+            timer: None,
+            timer_progress_bar: false,
+            close_on_route_change: false,
+            allow_enter_key: true,
+            key_bindings: Vec::new(),
+            stop_escape_propagation: true,
+            animation: SwalAnimation::default(),
+            show_class: SwalAnimationClass::default(),
+            hide_class: SwalAnimationClass::default(),
+            position: SwalPosition::default(),
+            grow: SwalGrow::default(),
+            width: S::default(),
+            padding: S::default(),
+            color: S::default(),
+            background: S::default(),
+            backdrop: SwalBackdrop::default(),
+            scrollbar_padding: true,
+            height_auto: true,
+            target: SwalTarget::default(),
+            scoped_to_target: false,
+            isolation: SwalIsolation::default(),
+            body: SwalBody::default(),
         }
     }
+
+    /// Whether or not the current options have a title.
+    pub fn has_title(&self) -> bool {
+        !self.title.as_ref().is_empty()
+    }
+
+    /// Whether or not the current options have a text.
+    pub fn has_text(&self) -> bool {
+        !self.text.as_ref().is_empty()
+    }
+
+    /// Whether or not the current options have a footer.
+    pub fn has_footer(&self) -> bool {
+        !self.footer.as_ref().is_empty()
+    }
+
+    /// Checks if the given text for the confirmation button is empty.
+    /// If it's empty, it means the default value, "Ok", should be used instead.
+    pub fn has_confirm_button_text(&self) -> bool {
+        !self.confirm_button_text.as_ref().is_empty()
+    }
+
+    /// Checks if the given text for the deny button is empty.
+    /// If it's empty, it means the default value, "Deny", should be used instead.
+    pub fn has_deny_button_text(&self) -> bool {
+        !self.deny_button_text.as_ref().is_empty()
+    }
+
+    /// Checks if the given text for the cancel button is empty.
+    /// If it's empty, it means the default value, "Cancel", should be used instead.
+    pub fn has_cancel_button_text(&self) -> bool {
+        !self.cancel_button_text.as_ref().is_empty()
+    }
+
+    /// Whether or not the backdrop covers only the target
+    /// (see the "scoped_to_target" option).
+    pub(crate) fn is_scoped(&self) -> bool {
+        self.scoped_to_target && self.target != SwalTarget::Body
+    }
+
+    /// Checks that the CSS options are valid values for their property.
+    pub(crate) fn check_css(&self) -> Result<(), SwalError> {
+        check_css("width", self.width.as_ref())?;
+        check_css("padding", self.padding.as_ref())?;
+        check_css("color", self.color.as_ref())?;
+        check_css("background", self.background.as_ref())?;
+        if let SwalBackdrop::Custom(background) = &self.backdrop {
+            check_css("background", background)?;
+        }
+        Ok(())
+    }
 }
 
 impl<S, I> SwalOptions<S, I>
@@ -201,32 +523,15 @@ where
             ..Self::default()
         }
     }
+}
 
-    /// Whether or not the current options have a title.
-    pub fn has_title(&self) -> bool {
-        !self.title.as_ref().is_empty()
-    }
-
-    /// Whether or not the current options have a text.
-    pub fn has_text(&self) -> bool {
-        !self.text.as_ref().is_empty()
-    }
-
-    /// Checks if the given text for the confirmation button is empty.
-    /// If it's empty, it means the default value, "Ok", should be used instead.
-    pub fn has_confirm_button_text(&self) -> bool {
-        !self.confirm_button_text.as_ref().is_empty()
-    }
-
-    /// Checks if the given text for the deny button is empty.
-    /// If it's empty, it means the default value, "Deny", should be used instead.
-    pub fn has_deny_button_text(&self) -> bool {
-        !self.deny_button_text.as_ref().is_empty()
-    }
-
-    /// Checks if the given text for the cancel button is empty.
-    /// If it's empty, it means the default value, "Cancel", should be used instead.
-    pub fn has_cancel_button_text(&self) -> bool {
-        !self.cancel_button_text.as_ref().is_empty()
+/// Gets the value of a CSS override (width, padding, color, background),
+/// or `None` if it's empty and the stylesheets should be used instead.
+pub(crate) fn css_override<S: AsRef<str>>(value: &S) -> Option<String> {
+    let value = value.as_ref();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
     }
 }
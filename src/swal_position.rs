@@ -0,0 +1,67 @@
+/// Where the popup should be placed on the screen.
+/// The backdrop still covers the whole viewport,
+/// only the container is moved.
+///
+/// The position is applied through a modifier class
+/// on the backdrop (`.swal-position-top`, `.swal-position-bottom-end`, etc.).
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// let opts = SwalOptions::<&str> {
+///     title: "Attached to the top edge",
+///     position: SwalPosition::Top,
+///     ..SwalOptions::default()
+/// };
+/// assert_eq!(opts.position.to_string(), "top");
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum SwalPosition {
+    /// At the top of the screen, horizontally centered.
+    Top,
+
+    /// In the top left corner (right in RTL layouts).
+    TopStart,
+
+    /// In the top right corner (left in RTL layouts).
+    TopEnd,
+
+    /// In the middle of the screen.
+    /// It is the default position.
+    #[default]
+    Center,
+
+    /// Vertically centered, on the left (right in RTL layouts).
+    CenterStart,
+
+    /// Vertically centered, on the right (left in RTL layouts).
+    CenterEnd,
+
+    /// At the bottom of the screen, horizontally centered.
+    Bottom,
+
+    /// In the bottom left corner (right in RTL layouts).
+    BottomStart,
+
+    /// In the bottom right corner (left in RTL layouts).
+    BottomEnd,
+}
+
+impl std::fmt::Display for SwalPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SwalPosition::Top => "top",
+            SwalPosition::TopStart => "top-start",
+            SwalPosition::TopEnd => "top-end",
+            SwalPosition::Center => "center",
+            SwalPosition::CenterStart => "center-start",
+            SwalPosition::CenterEnd => "center-end",
+            SwalPosition::Bottom => "bottom",
+            SwalPosition::BottomStart => "bottom-start",
+            SwalPosition::BottomEnd => "bottom-end",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -1,22 +1,31 @@
+use std::any::Any;
+
 use crate::SwalDismissReason;
+use crate::SwalValue;
 
 /// The data that is returned when an alert is closed.
-#[derive(Debug)]
-pub struct SwalResult {
+///
+/// The generic parameter (`T`) is the type of the value of a confirmed
+/// alert (see [`SwalValue`]). It's a simple `bool` by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwalResult<T = bool> {
     /// The "Confirm" button was clicked, the value will contain the result.
     pub is_confirmed: bool,
 
-    /// The "Deny" button was clicked, the value will be false.
+    /// The "Deny" button was clicked, there will be no value.
     pub is_denied: bool,
 
     /// The "Cancel" button was clicked, the dismiss will be
     /// [`SwalDismissReason.Cancel`]
     pub is_dismissed: bool,
 
-    /// The value from the popup, possible values:
-    /// - `true` for simple confirmed dialogs
-    /// - `false` for denied popups
-    pub value: bool,
+    /// The value of a confirmed popup, possible values:
+    /// - `Some(true)` for simple confirmed dialogs
+    /// - the value of the input, of the `async_pre_confirm` option
+    ///   or of [`Swal::close_with_value`](crate::Swal::close_with_value)
+    /// - `None` for denied and dismissed popups
+    pub value: Option<T>,
 
     /// The dismissal reason, see [`SwalDismissReason`].
     /// It's optional because if the popup is confirmed or denied, then it wasn't dismissed,
@@ -24,6 +33,65 @@ pub struct SwalResult {
     pub dismiss: Option<SwalDismissReason>,
 }
 
+/// The outcome of an alert, as an enum to be used with `match`
+/// (see [`SwalResult::into_outcome`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum SwalOutcome<T = bool> {
+    /// The popup was confirmed, with the given value.
+    Confirmed(T),
+
+    /// The popup was denied.
+    Denied,
+
+    /// The popup was dismissed for the given reason.
+    Dismissed(SwalDismissReason),
+}
+
+/// Displays the outcome of the alert, without its value:
+/// `confirmed`, `denied` or `dismissed (reason)`.
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// let r: SwalResult = SwalResult::canceled(SwalDismissReason::Timer);
+/// assert_eq!(r.to_string(), "dismissed (timer)");
+/// assert_eq!(SwalResult::confirmed().to_string(), "confirmed");
+/// ```
+impl<T> std::fmt::Display for SwalResult<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_confirmed {
+            write!(f, "confirmed")
+        } else if self.is_denied {
+            write!(f, "denied")
+        } else if let Some(reason) = self.dismiss {
+            write!(f, "dismissed ({})", reason)
+        } else {
+            write!(f, "dismissed")
+        }
+    }
+}
+
+impl<T> std::fmt::Display for SwalOutcome<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SwalOutcome::Confirmed(_) => write!(f, "confirmed"),
+            SwalOutcome::Denied => write!(f, "denied"),
+            SwalOutcome::Dismissed(reason) => write!(f, "dismissed ({})", reason),
+        }
+    }
+}
+
+/// A result whose type was erased, so that the results of all
+/// alerts can go through [`Swal::close`](crate::Swal::close).
+pub(crate) type AnyResult = SwalResult<Box<dyn Any>>;
+
 impl SwalResult {
     /// Creates a response that is the result of a confirmed popup.
     ///
@@ -34,15 +102,33 @@ impl SwalResult {
     ///
     /// let r = SwalResult::confirmed();
     /// assert!(r.is_confirmed);
-    /// assert!(r.value);
+    /// assert_eq!(r.value, Some(true));
     /// assert!(!r.is_denied);
     /// assert!(!r.is_dismissed);
     /// assert!(r.dismiss.is_none());
     /// ```
     pub fn confirmed() -> Self {
+        Self::confirmed_with(true)
+    }
+}
+
+impl<T> SwalResult<T> {
+    /// Creates a response that is the result of a popup confirmed with the given value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use leptos_sweetalert::*;
+    ///
+    /// let r = SwalResult::confirmed_with(String::from("John"));
+    /// assert!(r.is_confirmed);
+    /// assert_eq!(r.value.as_deref(), Some("John"));
+    /// assert!(r.dismiss.is_none());
+    /// ```
+    pub fn confirmed_with(value: T) -> Self {
         Self {
             is_confirmed: true,
-            value: true,
+            value: Some(value),
             is_denied: false,
             is_dismissed: false,
             dismiss: None,
@@ -56,9 +142,9 @@ impl SwalResult {
     /// ```
     /// # use leptos_sweetalert::*;
     ///
-    /// let r = SwalResult::denied();
+    /// let r: SwalResult = SwalResult::denied();
     /// assert!(!r.is_confirmed);
-    /// assert!(!r.value);
+    /// assert!(r.value.is_none());
     /// assert!(r.is_denied);
     /// assert!(!r.is_dismissed);
     /// assert!(r.dismiss.is_none());
@@ -66,7 +152,7 @@ impl SwalResult {
     pub fn denied() -> Self {
         Self {
             is_confirmed: false,
-            value: false,
+            value: None,
             is_denied: true,
             is_dismissed: false,
             dismiss: None,
@@ -80,9 +166,9 @@ impl SwalResult {
     /// ```
     /// # use leptos_sweetalert::*;
     ///
-    /// let r = SwalResult::canceled(SwalDismissReason::Backdrop);
+    /// let r: SwalResult = SwalResult::canceled(SwalDismissReason::Backdrop);
     /// assert!(!r.is_confirmed);
-    /// assert!(!r.value);
+    /// assert!(r.value.is_none());
     /// assert!(!r.is_denied);
     /// assert!(r.is_dismissed);
     /// assert!(r.dismiss.is_some());
@@ -91,10 +177,100 @@ impl SwalResult {
     pub fn canceled(reason: SwalDismissReason) -> Self {
         Self {
             is_confirmed: false,
-            value: false,
+            value: None,
             is_denied: false,
             is_dismissed: true,
             dismiss: Some(reason),
         }
     }
+
+    /// Gets the value of the popup if it was confirmed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use leptos_sweetalert::*;
+    ///
+    /// assert_eq!(SwalResult::confirmed_with(42).into_confirmed(), Some(42));
+    /// assert_eq!(SwalResult::<i32>::denied().into_confirmed(), None);
+    /// ```
+    pub fn into_confirmed(self) -> Option<T> {
+        if self.is_confirmed {
+            self.value
+        } else {
+            None
+        }
+    }
+
+    /// Erases the type of the value.
+    pub(crate) fn into_any(self) -> AnyResult
+    where
+        T: 'static,
+    {
+        SwalResult {
+            is_confirmed: self.is_confirmed,
+            is_denied: self.is_denied,
+            is_dismissed: self.is_dismissed,
+            value: self.value.map(|value| Box::new(value) as Box<dyn Any>),
+            dismiss: self.dismiss,
+        }
+    }
+}
+
+impl<T: SwalValue> SwalResult<T> {
+    /// Converts the result into a [`SwalOutcome`].
+    ///
+    /// A confirmed result without any value (which can only happen
+    /// when it was built manually) gets the value created
+    /// by [`SwalValue::from_input`] when there is no input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use leptos_sweetalert::*;
+    ///
+    /// let r = SwalResult::confirmed_with(String::from("John"));
+    /// match r.into_outcome() {
+    ///     SwalOutcome::Confirmed(name) => assert_eq!(name, "John"),
+    ///     SwalOutcome::Denied => panic!(),
+    ///     SwalOutcome::Dismissed(_reason) => panic!(),
+    /// }
+    ///
+    /// let r: SwalResult = SwalResult::canceled(SwalDismissReason::Esc);
+    /// assert_eq!(r.into_outcome(), SwalOutcome::Dismissed(SwalDismissReason::Esc));
+    /// ```
+    pub fn into_outcome(self) -> SwalOutcome<T> {
+        if self.is_confirmed {
+            SwalOutcome::Confirmed(self.value.unwrap_or_else(|| T::from_input(None)))
+        } else if self.is_denied {
+            SwalOutcome::Denied
+        } else {
+            // A dismissed result built manually may have no reason.
+            SwalOutcome::Dismissed(self.dismiss.unwrap_or(SwalDismissReason::Programmatic))
+        }
+    }
+}
+
+impl AnyResult {
+    /// Gets back the type of the value. A confirmed result whose value
+    /// isn't of the expected type (for example `Swal::close_with_value(42)`
+    /// on an alert expecting a `String`) gets its value from `fallback`.
+    pub(crate) fn downcast<T: SwalValue>(self, fallback: impl FnOnce() -> T) -> SwalResult<T> {
+        let value = self
+            .value
+            .and_then(|value| value.downcast::<T>().ok())
+            .map(|value| *value);
+        let value = if self.is_confirmed {
+            value.or_else(|| Some(fallback()))
+        } else {
+            value
+        };
+        SwalResult {
+            is_confirmed: self.is_confirmed,
+            is_denied: self.is_denied,
+            is_dismissed: self.is_dismissed,
+            value,
+            dismiss: self.dismiss,
+        }
+    }
 }
@@ -0,0 +1,49 @@
+/// The lifecycle of an alert (see [`SwalHandle::state`](crate::SwalHandle::state)).
+///
+/// An alert goes through these states in this order,
+/// but it can start closing before being fully opened.
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// assert!(SwalState::Opening.can_become(SwalState::Closing));
+/// assert!(!SwalState::Closed.can_become(SwalState::Open));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwalState {
+    /// The alert waits for the previous one to close,
+    /// or it was added to the DOM and its opening animation
+    /// is about to start.
+    Opening,
+
+    /// The alert is displayed.
+    Open,
+
+    /// The alert got its result and its closing animation is running.
+    Closing,
+
+    /// The alert was removed from the DOM,
+    /// or it was closed before it could be opened.
+    Closed,
+}
+
+impl SwalState {
+    /// Checks if an alert in this state can go to the given state.
+    pub fn can_become(self, next: SwalState) -> bool {
+        matches!(
+            (self, next),
+            (SwalState::Opening, SwalState::Open)
+                | (SwalState::Opening, SwalState::Closing)
+                | (SwalState::Open, SwalState::Closing)
+                | (SwalState::Closing, SwalState::Closed)
+        )
+    }
+
+    /// Whether or not the alert is opening or opened,
+    /// meaning that it didn't get its result yet.
+    pub fn is_active(self) -> bool {
+        matches!(self, SwalState::Opening | SwalState::Open)
+    }
+}
@@ -0,0 +1,52 @@
+use web_sys::Element;
+
+use crate::swal_error::{try_body, try_document};
+use crate::SwalError;
+
+/// The element in which the popup is added.
+///
+/// By default the popup is added at the end of `<body>` and its backdrop
+/// covers the whole viewport. Another container allows the popup to pick
+/// up the styles and the stacking context of this container, for example
+/// in a micro-frontend or in a shadow root.
+/// See also the "scoped_to_target" option of [`SwalOptions`](crate::SwalOptions)
+/// to make the backdrop cover only this container.
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// let opts = SwalOptions::<&str> {
+///     title: "Inside the sidebar",
+///     target: SwalTarget::Selector(String::from("#sidebar")),
+///     scoped_to_target: true,
+///     ..SwalOptions::default()
+/// };
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum SwalTarget {
+    /// The body of the document.
+    #[default]
+    Body,
+
+    /// The first element of the document matching the given CSS selector.
+    /// It must exist when the popup opens.
+    Selector(String),
+
+    /// The given element, which may be in a shadow root.
+    Element(Element),
+}
+
+impl SwalTarget {
+    /// Finds the element in which the popup must be added.
+    pub(crate) fn resolve(&self) -> Result<Element, SwalError> {
+        match self {
+            SwalTarget::Body => Ok(try_body()?.into()),
+            SwalTarget::Selector(selector) => try_document()?
+                .query_selector(selector)?
+                .ok_or_else(|| SwalError::TargetNotFound(selector.clone())),
+            SwalTarget::Element(element) => Ok(element.clone()),
+        }
+    }
+}
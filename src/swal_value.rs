@@ -0,0 +1,73 @@
+use leptos::*;
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+
+/// The type of the value that an alert gives
+/// when it's confirmed (see [`SwalResult`](crate::SwalResult)).
+///
+/// When the confirmation button is pressed, the value is created from
+/// the input of the popup (see [`Swal::get_input`](crate::Swal::get_input)),
+/// unless an `async_pre_confirm` was given, or unless the alert is closed
+/// with [`Swal::close_with_value`](crate::Swal::close_with_value).
+///
+/// # Example
+///
+/// ```
+/// # use leptos_sweetalert::*;
+///
+/// #[derive(Clone)]
+/// struct Age(u8);
+///
+/// impl SwalValue for Age {
+///     fn from_input(input: Option<&web_sys::HtmlElement>) -> Self {
+///         let value = input.map(|_| String::from("42")); // read the input here
+///         Age(value.and_then(|v| v.parse().ok()).unwrap_or_default())
+///     }
+/// }
+///
+/// let opt = SwalOptions::<&str, SwalIcon, Age> {
+///     title: "How old are you?",
+///     then: |result: SwalResult<Age>| {
+///         if let Some(Age(age)) = result.into_confirmed() {
+///             // ...
+///         }
+///     },
+///     ..SwalOptions::typed()
+/// };
+/// ```
+pub trait SwalValue: Clone + 'static {
+    /// Creates the value of a confirmed alert from its input,
+    /// or from nothing if the popup doesn't contain any input.
+    fn from_input(input: Option<&HtmlElement>) -> Self;
+}
+
+/// Always `true`, this is the value of the alerts
+/// that only need to know if they were confirmed.
+impl SwalValue for bool {
+    fn from_input(_input: Option<&HtmlElement>) -> Self {
+        true
+    }
+}
+
+impl SwalValue for () {
+    fn from_input(_input: Option<&HtmlElement>) -> Self {}
+}
+
+/// The value of the input, select or textarea,
+/// or an empty string if there is none.
+impl SwalValue for String {
+    fn from_input(input: Option<&HtmlElement>) -> Self {
+        let Some(input) = input else {
+            return String::new();
+        };
+        if let Some(input) = input.dyn_ref::<web_sys::HtmlInputElement>() {
+            input.value()
+        } else if let Some(textarea) = input.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+            textarea.value()
+        } else if let Some(select) = input.dyn_ref::<web_sys::HtmlSelectElement>() {
+            select.value()
+        } else {
+            String::new()
+        }
+    }
+}
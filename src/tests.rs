@@ -1,8 +1,48 @@
 #[cfg(test)]
+#[allow(
+    clippy::module_inception,
+    clippy::bool_assert_comparison,
+    clippy::assertions_on_constants
+)]
 mod tests {
     use crate::SwalOptions;
     use crate::SwalIconLike;
     use crate::SwalIcon;
+    use crate::SwalPosition;
+    use crate::SwalGrow;
+    use crate::SwalBackdrop;
+    use crate::SwalAnimationClass;
+    use crate::SwalAnimationPreset;
+    use crate::SwalAnimation;
+    use crate::swal_options::css_override;
+    use crate::scroll_lock::{parse_px, unlock_scroll};
+    use crate::focus::sort_by_tab_index;
+    use crate::transition::{longest_time, parse_css_times};
+    use std::time::Duration;
+    use crate::{SwalHandle, SwalResult};
+    use crate::{SwalDismissReason, SwalOutcome};
+    use crate::Swal;
+    use crate::SwalError;
+    use crate::SwalTarget;
+    use crate::SwalIsolation;
+    use crate::SwalStylesheet;
+    use crate::{SwalBody, SwalContext};
+    use crate::SwalState;
+    use leptos::{as_child_of_current_owner, create_node_ref, create_rw_signal, on_cleanup, SignalGetUntracked};
+    use crate::swal_error::check_css;
+    use crate::registry::{self, SwalInstance};
+    use crate::inert::{InertStack, InertState};
+    use crate::Swal::SwalButton;
+    use std::cell::RefCell;
+    use std::future::Future;
+    use std::task::{Context, Poll, Waker};
+    use crate::{KeyCombo, SwalAction};
+    use crate::registry::{SwalHook, SwalHooks};
+    use std::rc::Rc;
+    use web_sys::wasm_bindgen::{JsCast, JsValue};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::Wake;
 
     #[test]
     fn test_default_swal() {
@@ -90,4 +130,834 @@ mod tests {
         };
         (opts.pre_deny)();
     }
+
+    #[test]
+    fn test_default_position_and_grow() {
+        let opts = SwalOptions::<&str>::default();
+        assert_eq!(opts.position, SwalPosition::Center);
+        assert_eq!(opts.grow, SwalGrow::None);
+    }
+
+    #[test]
+    fn test_position_and_grow_class_names() {
+        assert_eq!(SwalPosition::TopStart.to_string(), "top-start");
+        assert_eq!(SwalPosition::CenterEnd.to_string(), "center-end");
+        assert_eq!(SwalPosition::Bottom.to_string(), "bottom");
+        assert_eq!(SwalGrow::Fullscreen.to_string(), "fullscreen");
+    }
+
+    #[test]
+    fn test_css_overrides() {
+        let opts = SwalOptions::<&str> {
+            width: "400px",
+            ..SwalOptions::default()
+        };
+        assert_eq!(css_override(&opts.width), Some(String::from("400px")));
+        assert_eq!(css_override(&opts.padding), None);
+        assert!(SwalBackdrop::Default.is_modal());
+        assert!(SwalBackdrop::Custom(String::from("red")).is_modal());
+        assert!(!SwalBackdrop::None.is_modal());
+    }
+
+    #[test]
+    fn test_scroll_lock_defaults() {
+        let opts = SwalOptions::<&str>::default();
+        assert!(opts.scrollbar_padding);
+        assert!(opts.height_auto);
+        assert_eq!(parse_px("15px"), 15.0);
+        assert_eq!(parse_px(" 12.5px "), 12.5);
+        assert_eq!(parse_px("auto"), 0.0);
+        // The page isn't locked, so the document isn't needed.
+        assert_eq!(unlock_scroll(), Ok(()));
+    }
+
+    #[test]
+    fn test_sort_by_tab_index() {
+        let sorted = sort_by_tab_index(vec![(0, "a"), (2, "b"), (0, "c"), (1, "d"), (2, "e")]);
+        assert_eq!(sorted, vec!["d", "b", "e", "a", "c"]);
+    }
+
+    #[test]
+    fn test_parse_css_times() {
+        assert_eq!(parse_css_times("0.2s"), vec![0.2]);
+        assert_eq!(parse_css_times("200ms, 1s, 0s"), vec![0.2, 1.0, 0.0]);
+        assert_eq!(parse_css_times(""), Vec::<f64>::new());
+        assert_eq!(parse_css_times("invalid"), vec![0.0]);
+    }
+
+    #[test]
+    fn test_longest_time() {
+        assert_eq!(longest_time(&[0.2], &[0.0]), Duration::from_millis(200));
+        // The delays are repeated to match the number of durations.
+        assert_eq!(longest_time(&[0.1, 0.3], &[0.5]), Duration::from_millis(800));
+        assert_eq!(longest_time(&[0.0], &[]), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_animation_classes() {
+        let opts = SwalOptions::<&str>::default();
+        assert_eq!(opts.show_class.show_classes(), "");
+        assert_eq!(opts.hide_class.hide_classes(), "");
+        let preset = SwalAnimationClass::Preset(SwalAnimationPreset::SlideUp);
+        assert_eq!(preset.show_classes(), "swal-show-slide-up");
+        assert_eq!(preset.hide_classes(), "swal-hide-slide-up");
+    }
+
+    #[test]
+    fn test_reduced_motion() {
+        assert!(SwalAnimation::On.resolve(true));
+        assert!(!SwalAnimation::Off.resolve(false));
+        assert!(SwalAnimation::Auto.resolve(false));
+        assert!(!SwalAnimation::Auto.resolve(true));
+    }
+
+    #[test]
+    fn test_handle_result() {
+        let handle = SwalHandle::<bool>::new();
+        let mut result = Box::pin(handle.result());
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(result.as_mut().poll(&mut cx).is_pending());
+        handle.resolve(Some(SwalResult::denied()));
+        match result.as_mut().poll(&mut cx) {
+            Poll::Ready(Some(r)) => assert!(r.is_denied),
+            _ => panic!("The result should be available"),
+        }
+        assert!(handle.is_closed());
+        assert!(!handle.is_open());
+    }
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_handle_wakers() {
+        let handle = SwalHandle::<bool>::new();
+        let mut result = Box::pin(handle.result());
+        let first = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let second = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let first_waker = Waker::from(first.clone());
+        let second_waker = Waker::from(second.clone());
+
+        // Polling again with the same waker doesn't register it twice.
+        assert!(result.as_mut().poll(&mut Context::from_waker(&first_waker)).is_pending());
+        assert!(result.as_mut().poll(&mut Context::from_waker(&first_waker)).is_pending());
+        // Only the last waker is woken.
+        assert!(result.as_mut().poll(&mut Context::from_waker(&second_waker)).is_pending());
+
+        // A dropped future isn't woken.
+        let mut dropped = Box::pin(handle.result());
+        assert!(dropped.as_mut().poll(&mut Context::from_waker(&first_waker)).is_pending());
+        drop(dropped);
+
+        assert!(handle.close(None));
+        assert_eq!(first.0.load(Ordering::SeqCst), 0);
+        assert_eq!(second.0.load(Ordering::SeqCst), 1);
+        assert!(matches!(result.as_mut().poll(&mut Context::from_waker(&second_waker)), Poll::Ready(None)));
+    }
+
+    #[test]
+    fn test_handle_closed_before_opening() {
+        let handle = SwalHandle::<bool>::new();
+        // The alert isn't opened yet, so it gets canceled.
+        assert!(handle.close(Some(SwalResult::confirmed())));
+        assert!(handle.is_closed());
+        assert!(!handle.close(None));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(matches!(Box::pin(handle.result()).as_mut().poll(&mut cx), Poll::Ready(None)));
+    }
+
+    #[test]
+    fn test_typed_result() {
+        let r = SwalResult::confirmed_with(String::from("John")).into_any();
+        assert_eq!(r.downcast(|| String::from("input")).into_confirmed().as_deref(), Some("John"));
+
+        // A value of the wrong type is replaced by the one of the input.
+        let r = SwalResult::confirmed_with(42).into_any();
+        assert_eq!(r.downcast(|| String::from("input")).value.as_deref(), Some("input"));
+
+        let r = SwalResult::<bool>::denied().into_any();
+        assert!(r.downcast(|| String::from("input")).value.is_none());
+
+        let r: SwalResult<String> = SwalResult::canceled(SwalDismissReason::Backdrop);
+        assert_eq!(r.into_outcome(), SwalOutcome::Dismissed(SwalDismissReason::Backdrop));
+        assert_eq!(SwalResult::confirmed().into_outcome(), SwalOutcome::Confirmed(true));
+    }
+
+    #[test]
+    fn test_typed_handle() {
+        let handle = SwalHandle::<String>::new();
+        handle.resolve(Some(SwalResult::confirmed_with(String::from("John"))));
+        let mut cx = Context::from_waker(Waker::noop());
+        match Box::pin(handle.result()).as_mut().poll(&mut cx) {
+            Poll::Ready(Some(r)) => assert_eq!(r.into_confirmed().as_deref(), Some("John")),
+            _ => panic!("The result should be available"),
+        }
+    }
+
+    #[test]
+    fn test_dismiss_reasons() {
+        let reasons = [
+            SwalDismissReason::Backdrop,
+            SwalDismissReason::Cancel,
+            SwalDismissReason::Close,
+            SwalDismissReason::Esc,
+            SwalDismissReason::Timer,
+            SwalDismissReason::Programmatic,
+            SwalDismissReason::Replaced,
+            SwalDismissReason::RouteChange,
+        ];
+        let names: std::collections::HashSet<String> = reasons.iter().map(|r| r.to_string()).collect();
+        assert_eq!(names.len(), reasons.len());
+        let reasons: std::collections::HashSet<SwalDismissReason> = reasons.into_iter().collect();
+        assert!(reasons.contains(&SwalDismissReason::Timer));
+
+        let r: SwalResult = SwalResult::canceled(SwalDismissReason::RouteChange);
+        let copy = r;
+        assert_eq!(r, copy);
+        assert_eq!(r.to_string(), "dismissed (route-change)");
+        assert_eq!(SwalOutcome::<bool>::Denied.to_string(), "denied");
+        // Closing the alert on navigation is opt-in.
+        assert!(!SwalOptions::<&str>::default().close_on_route_change);
+    }
+
+    thread_local! {
+        static EVENTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn log_event(event: String) {
+        EVENTS.with(|e| e.borrow_mut().push(event));
+    }
+
+    /// Registers an alert that isn't in the DOM, as `fire` would do.
+    fn register_alert(then: fn(SwalResult)) -> (String, SwalHandle) {
+        let handle = SwalHandle::<bool>::new();
+        let id = registry::next_id();
+        registry::register(SwalInstance::new(
+            id.clone(),
+            Swal::make_resolver(handle.clone(), then),
+        ));
+        (id, handle)
+    }
+
+    #[test]
+    fn test_replaced_alert_is_resolved_first() {
+        assert!(!Swal::must_wait());
+        let (_, first) = register_alert(|r| log_event(format!("first {}", r)));
+        assert!(Swal::must_wait());
+
+        // This is what `fire` does when an alert is still opened.
+        Swal::replace_then(Swal::replaced_parent(), |parent| {
+            assert_eq!(parent, None);
+            log_event(String::from("second opened"));
+        });
+        assert!(first.is_closed());
+        assert!(!Swal::must_wait());
+
+        // An alert fired from a pushed one stays above the same parent.
+        let (parent_id, parent) = register_alert(|r| log_event(format!("parent {}", r)));
+        let (child_id, child) = register_alert(|r| log_event(format!("child {}", r)));
+        registry::with_instance(&child_id, |i| i.parent = Some(parent_id.clone()));
+        let expected = parent_id.clone();
+        Swal::replace_then(Swal::replaced_parent(), move |parent| {
+            assert_eq!(parent, Some(expected));
+            log_event(String::from("sibling opened"));
+        });
+        assert!(child.is_closed());
+        assert!(!parent.is_closed());
+        assert!(Swal::close_id(&parent_id, None));
+
+        let events = EVENTS.with(|e| e.take());
+        assert_eq!(
+            events,
+            [
+                "first dismissed (replaced)",
+                "second opened",
+                "child dismissed (replaced)",
+                "sibling opened"
+            ]
+        );
+    }
+
+    thread_local! {
+        static REPLACED_PARENT: RefCell<Option<Option<String>>> = const { RefCell::new(None) };
+    }
+
+    #[test]
+    fn test_alert_fired_from_a_closing_child() {
+        let (parent_id, parent) = register_alert(|r| log_event(format!("parent {}", r)));
+
+        // `fire` called from the "then" callback of a pushed alert,
+        // which is already closing: the parent remains.
+        let (child_id, child) = register_alert(|r| {
+            log_event(format!("child {}", r));
+            REPLACED_PARENT.with(|p| p.replace(Some(Swal::replaced_parent())));
+        });
+        registry::with_instance(&child_id, |i| i.parent = Some(parent_id.clone()));
+        assert!(Swal::close_id(&child_id, Some(SwalResult::confirmed().into_any())));
+        assert!(child.is_closed());
+        let replaced_parent = REPLACED_PARENT.with(|p| p.take()).unwrap();
+        assert_eq!(replaced_parent.as_ref(), Some(&parent_id));
+        let expected = parent_id.clone();
+        Swal::replace_then(replaced_parent, move |parent| {
+            assert_eq!(parent, Some(expected));
+            log_event(String::from("sibling opened"));
+        });
+        assert!(!parent.is_closed());
+
+        // `fire` called from the "pre_confirm" callback of a pushed alert,
+        // which closes right after.
+        let (child_id, child) = register_alert(|r| log_event(format!("child {}", r)));
+        registry::with_instance(&child_id, |i| i.parent = Some(parent_id.clone()));
+        let replaced_parent = Swal::replaced_parent();
+        assert!(Swal::close_id(&child_id, Some(SwalResult::confirmed().into_any())));
+        let expected = parent_id.clone();
+        Swal::replace_then(replaced_parent, move |parent| {
+            assert_eq!(parent, Some(expected));
+            log_event(String::from("sibling opened"));
+        });
+        assert!(child.is_closed());
+        assert!(!parent.is_closed());
+
+        // Outside of a callback, the topmost alert is replaced.
+        Swal::replace_then(Swal::replaced_parent(), |parent| {
+            assert_eq!(parent, None);
+            log_event(String::from("replacement opened"));
+        });
+        assert!(parent.is_closed());
+        assert_eq!(
+            EVENTS.with(|e| e.take()),
+            [
+                "child confirmed",
+                "sibling opened",
+                "child confirmed",
+                "sibling opened",
+                "parent dismissed (replaced)",
+                "replacement opened"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_alert_stack() {
+        let (bottom_id, bottom) = register_alert(|r| log_event(format!("bottom {}", r)));
+        let (top_id, top) = register_alert(|r| log_event(format!("top {}", r)));
+        assert_ne!(bottom_id, top_id);
+        assert!(bottom_id.starts_with("swal-"));
+        assert_eq!(registry::topmost().as_ref(), Some(&top_id));
+
+        // An alert that isn't the topmost one can be closed by its ID.
+        assert!(Swal::close_id(&bottom_id, Some(SwalResult::<bool>::denied().into_any())));
+        assert!(!Swal::close_id(&bottom_id, None));
+        assert!(bottom.is_closed());
+        assert!(!top.is_closed());
+        assert_eq!(registry::topmost().as_ref(), Some(&top_id));
+
+        assert!(Swal::replace_topmost());
+        assert!(top.is_closed());
+        assert_eq!(registry::topmost(), None);
+
+        let events = EVENTS.with(|e| e.take());
+        assert_eq!(events, ["bottom denied", "top dismissed (replaced)"]);
+    }
+
+    #[test]
+    fn test_pushed_alert() {
+        let (parent_id, parent) = register_alert(|r| log_event(format!("parent {}", r)));
+        let child = SwalHandle::<bool>::new();
+        let mut instance = SwalInstance::new(
+            registry::next_id(),
+            Swal::make_resolver(child.clone(), |r| log_event(format!("child {}", r))),
+        );
+        instance.parent = Some(parent_id.clone());
+        let child_id = instance.id.clone();
+        registry::register(instance);
+        assert!(registry::has_child(&parent_id));
+        assert_eq!(registry::topmost_parent().as_ref(), Some(&parent_id));
+
+        // The parent comes back, untouched, when the child closes.
+        assert!(Swal::close_id(&child_id, Some(SwalResult::confirmed().into_any())));
+        assert!(child.is_closed());
+        assert!(!parent.is_closed());
+        assert!(!registry::has_child(&parent_id));
+        assert_eq!(registry::topmost().as_ref(), Some(&parent_id));
+        assert_eq!(registry::topmost_parent(), None);
+
+        assert!(Swal::close_id(&parent_id, Some(SwalResult::<bool>::denied().into_any())));
+        let events = EVENTS.with(|e| e.take());
+        assert_eq!(events, ["child confirmed", "parent denied"]);
+    }
+
+    #[test]
+    fn test_swal_context() {
+        let context = SwalContext::<String>::new(String::from("swal-0"), create_node_ref());
+        assert!(context.is_confirm_enabled().get_untracked());
+        context.set_confirm_enabled(false);
+        assert!(!context.is_confirm_enabled().get_untracked());
+        assert_eq!(context.value(), None);
+        context.set_value(String::from("John"));
+        assert_eq!(context.value().as_deref(), Some("John"));
+        // This popup isn't opened.
+        assert!(!context.close(None));
+
+        let opts = SwalOptions::<&str> {
+            body: SwalBody::from(|| "Hello"),
+            ..SwalOptions::default()
+        };
+        assert_eq!(format!("{:?}", opts.body), "SwalBody(..)");
+    }
+
+    #[test]
+    fn test_key_listener() {
+        let mut listener = Swal::KeyListener::<&str, u32>::new();
+        assert_eq!(listener.uninstall(), None);
+        // The listener is added once, whatever the number of alerts.
+        listener.install(|| "first");
+        listener.install(|| "second");
+
+        // Each event is handled once, even if several listeners receive it.
+        assert!(listener.is_new(1));
+        assert!(!listener.is_new(1));
+        assert!(listener.is_new(2));
+
+        assert_eq!(listener.uninstall(), Some("first"));
+        assert_eq!(listener.uninstall(), None);
+        // The last event was forgotten.
+        assert!(listener.is_new(2));
+        listener.install(|| "third");
+        assert_eq!(listener.uninstall(), Some("third"));
+    }
+
+    struct FakeButton {
+        locked: std::cell::Cell<bool>,
+        disabled: std::cell::Cell<bool>,
+    }
+
+    impl SwalButton for &FakeButton {
+        fn is_locked(&self) -> bool {
+            self.locked.get()
+        }
+
+        fn set_disabled(&self, disabled: bool) {
+            self.disabled.set(disabled);
+        }
+    }
+
+    #[test]
+    fn test_locked_buttons_remain_disabled() {
+        let confirm = FakeButton { locked: true.into(), disabled: true.into() };
+        let deny = FakeButton { locked: false.into(), disabled: false.into() };
+
+        // This is what `show_loading` and `disable_buttons` do.
+        Swal::disable_buttons_of([&confirm, &deny], true);
+        assert!(confirm.disabled.get());
+        assert!(deny.disabled.get());
+
+        // This is what `hide_loading` and `enable_buttons` do.
+        Swal::disable_buttons_of([&confirm, &deny], false);
+        assert!(confirm.disabled.get());
+        assert!(!deny.disabled.get());
+
+        confirm.locked.set(false);
+        Swal::disable_buttons_of([&confirm, &deny], false);
+        assert!(!confirm.disabled.get());
+    }
+
+    #[test]
+    fn test_buttons_run_pre_callbacks_then_then() {
+        let then = |r: SwalResult| log_event(format!("then {}", r));
+
+        let (id, handle) = register_alert(then);
+        let context = SwalContext::<bool>::new(id.clone(), create_node_ref());
+        Swal::confirm_clicked(&id, || log_event(String::from("pre_confirm")), None, true, &context);
+        assert!(handle.is_closed());
+
+        let (id, handle) = register_alert(then);
+        Swal::deny_clicked::<bool>(&id, || log_event(String::from("pre_deny")), true);
+        assert!(handle.is_closed());
+
+        // Without auto_close, the alert remains opened.
+        let (id, handle) = register_alert(then);
+        let context = SwalContext::<bool>::new(id.clone(), create_node_ref());
+        Swal::confirm_clicked(&id, || log_event(String::from("pre_confirm")), None, false, &context);
+        Swal::deny_clicked::<bool>(&id, || log_event(String::from("pre_deny")), false);
+        Swal::dismiss_clicked(&id, SwalDismissReason::Cancel, false, then);
+        assert!(!handle.is_closed());
+        Swal::dismiss_clicked(&id, SwalDismissReason::Close, true, then);
+        assert!(handle.is_closed());
+
+        // An alert above this one keeps it opened.
+        let (parent_id, parent) = register_alert(then);
+        let (child_id, _) = register_alert(then);
+        registry::with_instance(&child_id, |i| i.parent = Some(parent_id.clone()));
+        Swal::deny_clicked::<bool>(&parent_id, || log_event(String::from("pre_deny")), true);
+        assert!(!parent.is_closed());
+        assert!(Swal::close_id(&child_id, None));
+        assert!(Swal::close_id(&parent_id, None));
+
+        let events = EVENTS.with(|e| e.take());
+        assert_eq!(
+            events,
+            [
+                "pre_confirm",
+                "then confirmed",
+                "pre_deny",
+                "then denied",
+                "pre_confirm",
+                "pre_deny",
+                "then dismissed (cancel)",
+                "then dismissed (close)",
+                "pre_deny",
+            ]
+        );
+    }
+
+    thread_local! {
+        static CLEANUPS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    #[test]
+    fn test_owners_are_disposed() {
+        // Until an alert was opened, the alerts open once the current code is done,
+        // since it cannot be told apart from an effect.
+        assert!(Swal::must_defer());
+        for _ in 0..100 {
+            // The alert is fired from an effect, or from a component
+            // that will be unmounted while the alert is still opened.
+            let (id, effect) = as_child_of_current_owner(|()| {
+                // So it opens right after, outside of this owner.
+                assert!(Swal::must_defer());
+                register_alert(|_| {}).0
+            })(());
+
+            // This is what `try_open` does once the alert opens, from a timeout.
+            let (signal, owner) = Swal::create_root(|| {
+                on_cleanup(|| CLEANUPS.with(|c| c.set(c.get() + 1)));
+                create_rw_signal(0)
+            });
+            registry::with_instance(&id, |instance| instance.owner = Some(owner));
+            assert!(!Swal::must_defer());
+            drop(effect);
+            assert!(signal.try_get_untracked().is_some());
+
+            // It's disposed once the alert is removed from the DOM.
+            assert!(Swal::close_id(&id, None));
+            assert!(signal.try_get_untracked().is_none());
+        }
+        assert_eq!(CLEANUPS.with(|c| c.get()), 100);
+        assert_eq!(registry::topmost(), None);
+    }
+
+    #[test]
+    fn test_state_machine() {
+        let states = [SwalState::Opening, SwalState::Open, SwalState::Closing, SwalState::Closed];
+        // Each state can only go forward.
+        for (i, state) in states.iter().enumerate() {
+            for (j, next) in states.iter().enumerate() {
+                if state.can_become(*next) {
+                    assert!(j > i);
+                }
+            }
+        }
+        assert!(SwalState::Opening.can_become(SwalState::Closing));
+        assert!(!SwalState::Opening.can_become(SwalState::Closed));
+
+        let (id, handle) = register_alert(|_| {});
+        assert_eq!(registry::state_of(&id), SwalState::Opening);
+        assert_eq!(handle.state(), SwalState::Opening);
+        assert_eq!(registry::with_instance(&id, |i| i.transition(SwalState::Open)), Some(true));
+        assert_eq!(registry::with_instance(&id, |i| i.transition(SwalState::Opening)), Some(false));
+        assert!(registry::is_active(&id));
+
+        // An alert that isn't in the DOM is closed right away.
+        assert!(Swal::close_id(&id, None));
+        assert_eq!(registry::state_of(&id), SwalState::Closed);
+        assert_eq!(handle.state(), SwalState::Closed);
+        assert!(!Swal::close_id(&id, None));
+        // The key handlers are removed along with the last alert.
+        assert!(registry::is_empty());
+    }
+
+    #[test]
+    fn test_css_errors() {
+        // Empty values are not checked, the stylesheets are used instead.
+        assert!(check_css("width", "").is_ok());
+        assert!(SwalOptions::<&str>::default().check_css().is_ok());
+        let error = SwalError::InvalidCss {
+            property: String::from("color"),
+            value: String::from("blurple"),
+        };
+        assert_eq!(error.to_string(), "invalid value for the CSS property \"color\": \"blurple\"");
+        assert_eq!(SwalError::NoBody.to_string(), "could not find the body of the document");
+    }
+
+    #[test]
+    fn test_scoped_target() {
+        let opts = SwalOptions::<&str> {
+            scoped_to_target: true,
+            ..SwalOptions::default()
+        };
+        // The body cannot be a scope.
+        assert!(!opts.is_scoped());
+        let opts = SwalOptions::<&str> {
+            target: SwalTarget::Selector(String::from("#app")),
+            ..opts
+        };
+        assert!(opts.is_scoped());
+        assert_eq!(SwalTarget::default(), SwalTarget::Body);
+        assert_eq!(opts.isolation, SwalIsolation::None);
+    }
+
+    #[test]
+    fn test_isolation_stylesheets() {
+        assert!(SwalIsolation::None.check().is_ok());
+        let isolation = SwalIsolation::ShadowDom(vec![SwalStylesheet::Url(String::from("swal.css"))]);
+        assert!(isolation.check().is_ok());
+        // Without stylesheets, the isolated popup would have no styles.
+        assert_eq!(SwalIsolation::ShadowDom(Vec::new()).check(), Err(SwalError::NoStylesheet));
+        assert_eq!(SwalError::NoStylesheet.to_string(), "the isolated popup has no stylesheet");
+    }
+
+    #[test]
+    fn test_key_combos() {
+        let combo = KeyCombo::from("Ctrl+Shift+D");
+        assert_eq!(combo, KeyCombo::new("D").ctrl().shift());
+        // The key is compared regardless of the case,
+        // but the modifiers must be exactly the same.
+        assert!(combo.matches_key("d", [true, false, true, false]));
+        assert!(combo.matches_key("D", [true, false, true, false]));
+        assert!(!combo.matches_key("d", [true, false, false, false]));
+        assert!(!combo.matches_key("d", [true, true, true, false]));
+        assert!(!combo.matches_key("e", [true, false, true, false]));
+        assert!(!combo.is_typed());
+        assert_eq!(combo.to_string(), "Ctrl+Shift+D");
+
+        let combo = KeyCombo::from("d");
+        assert_eq!(combo, KeyCombo::new("d"));
+        assert!(combo.matches_key("D", [false; 4]));
+        assert!(!combo.matches_key("d", [false, false, false, true]));
+        assert!(combo.is_typed());
+        assert!(KeyCombo::new("d").shift().is_typed());
+
+        assert_eq!(KeyCombo::from("cmd + Enter"), KeyCombo::new("Enter").meta());
+        assert_eq!(KeyCombo::from("Alt++"), KeyCombo::new("+").alt());
+        assert_eq!(KeyCombo::from("+"), KeyCombo::new("+"));
+
+        let opts = SwalOptions::<&str>::default();
+        assert!(opts.allow_enter_key);
+        assert!(opts.key_bindings.is_empty());
+        assert!(opts.stop_escape_propagation);
+        assert_eq!(SwalAction::Deny.button_selector(), ".swal-deny-button");
+    }
+
+    #[test]
+    fn test_footer_and_close_button() {
+        let opts = SwalOptions::<&str>::default();
+        assert!(!opts.has_footer());
+        assert!(!opts.show_close_button);
+        let opts = SwalOptions::<&str> {
+            footer: "Need help?",
+            show_close_button: true,
+            ..opts
+        };
+        assert!(opts.has_footer());
+        assert_eq!(SwalDismissReason::Close.to_string(), "close");
+
+        // Without an opened alert, the validation message is ignored.
+        Swal::show_validation_message("This field is required");
+        Swal::reset_validation_message();
+        assert!(Swal::get_validation_message().is_none());
+        assert!(Swal::get_footer().is_none());
+        assert!(Swal::get_close_button().is_none());
+    }
+
+    /// Gives hooks logging their name to an alert that isn't in the DOM,
+    /// with a placeholder as popup.
+    fn set_logging_hooks(id: &str) {
+        registry::with_instance(id, |instance| {
+            instance.hooks = Some(SwalHooks {
+                will_open: |_| log_event(String::from("will_open")),
+                did_render: |_| log_event(String::from("did_render")),
+                did_open: |_| log_event(String::from("did_open")),
+                will_close: |_| log_event(String::from("will_close")),
+                did_close: |_| log_event(String::from("did_close")),
+                did_destroy: |_| log_event(String::from("did_destroy")),
+            });
+            instance.popup = Some(Rc::new(JsValue::NULL.unchecked_into()));
+        });
+    }
+
+    #[test]
+    fn test_hooks_order() {
+        // This is what `try_open` does once the alert is mounted and then opened.
+        let (id, _) = register_alert(|r| log_event(format!("then {}", r)));
+        set_logging_hooks(&id);
+        Swal::call_hook(&id, SwalHook::WillOpen);
+        Swal::call_hook(&id, SwalHook::DidRender);
+        Swal::call_hook(&id, SwalHook::DidOpen);
+        // Each hook is called once, and never after a later one.
+        Swal::call_hook(&id, SwalHook::DidOpen);
+        Swal::call_hook(&id, SwalHook::DidRender);
+        assert!(Swal::close_id(&id, Some(SwalResult::confirmed().into_any())));
+        assert!(!Swal::close_id(&id, None));
+        assert_eq!(
+            EVENTS.with(|e| e.take()),
+            ["will_open", "did_render", "did_open", "then confirmed", "will_close", "did_close", "did_destroy"]
+        );
+
+        // An alert closed while it's opening doesn't call "did_open".
+        let (id, _) = register_alert(|_| {});
+        set_logging_hooks(&id);
+        Swal::call_hook(&id, SwalHook::WillOpen);
+        Swal::call_hook(&id, SwalHook::DidRender);
+        assert!(Swal::close_id(&id, None));
+        Swal::call_hook(&id, SwalHook::DidOpen);
+        assert_eq!(
+            EVENTS.with(|e| e.take()),
+            ["will_open", "did_render", "will_close", "did_close", "did_destroy"]
+        );
+    }
+
+    #[test]
+    fn test_hooks_of_replaced_and_pushed_alerts() {
+        // The replaced alert is destroyed before the new one opens.
+        let (id, _) = register_alert(|r| log_event(format!("first {}", r)));
+        set_logging_hooks(&id);
+        Swal::call_hook(&id, SwalHook::DidOpen);
+        Swal::replace_then(Swal::replaced_parent(), |_| log_event(String::from("second opened")));
+        assert_eq!(
+            EVENTS.with(|e| e.take()),
+            ["did_open", "first dismissed (replaced)", "will_close", "did_close", "did_destroy", "second opened"]
+        );
+
+        // The hooks of the parent aren't called by its child.
+        let (parent_id, _) = register_alert(|_| {});
+        set_logging_hooks(&parent_id);
+        let (child_id, _) = register_alert(|r| log_event(format!("child {}", r)));
+        registry::with_instance(&child_id, |i| i.parent = Some(parent_id.clone()));
+        assert!(Swal::close_id(&child_id, Some(SwalResult::confirmed().into_any())));
+        assert_eq!(EVENTS.with(|e| e.take()), ["child confirmed"]);
+        assert!(Swal::close_id(&parent_id, None));
+        assert_eq!(
+            EVENTS.with(|e| e.take()),
+            ["will_close", "did_close", "did_destroy"]
+        );
+    }
+
+    /// The "inert" and "aria-hidden" attributes of the elements of a page.
+    type Attributes = std::collections::HashMap<&'static str, (Option<String>, Option<String>)>;
+
+    /// What `make_siblings_inert` does when the given Swal opens.
+    fn make_inert(
+        stack: &mut InertStack<&'static str>,
+        page: &mut Attributes,
+        swal: &'static str,
+        siblings: &[&'static str],
+    ) {
+        let siblings = siblings
+            .iter()
+            .map(|&element| {
+                let made_inert = (Some(String::new()), Some(String::from("true")));
+                let (inert, aria_hidden) = page.insert(element, made_inert).unwrap();
+                InertState { element, inert, aria_hidden }
+            })
+            .collect();
+        stack.push(swal, siblings);
+    }
+
+    /// What `restore_siblings` does when the given Swal closes.
+    fn restore(stack: &mut InertStack<&'static str>, page: &mut Attributes, swal: &'static str) {
+        for state in stack.remove(&swal, |a, b| a == b) {
+            page.insert(state.element, (state.inert, state.aria_hidden));
+        }
+    }
+
+    fn initial_page() -> Attributes {
+        Attributes::from([
+            ("header", (None, None)),
+            ("main", (None, Some(String::from("false")))),
+            ("aside", (Some(String::new()), None)),
+        ])
+    }
+
+    #[test]
+    fn test_nested_inert_elements() {
+        let mut stack = InertStack::new();
+        let mut page = initial_page();
+
+        // A pushed alert closing before its parent.
+        make_inert(&mut stack, &mut page, "parent", &["header", "main", "aside"]);
+        make_inert(&mut stack, &mut page, "child", &["header", "main", "aside"]);
+        restore(&mut stack, &mut page, "child");
+        assert_eq!(page["main"], (Some(String::new()), Some(String::from("true"))));
+        restore(&mut stack, &mut page, "parent");
+        assert_eq!(page, initial_page());
+
+        // An alert that didn't make anything inert restores nothing.
+        restore(&mut stack, &mut page, "parent");
+        assert_eq!(page, initial_page());
+    }
+
+    #[test]
+    fn test_inert_elements_restored_out_of_order() {
+        let mut stack = InertStack::new();
+        let mut page = initial_page();
+
+        // The alert below closes first, for example when a queued
+        // alert opens while the previous one is still closing.
+        make_inert(&mut stack, &mut page, "first", &["header", "main"]);
+        make_inert(&mut stack, &mut page, "second", &["header", "main", "aside"]);
+        restore(&mut stack, &mut page, "first");
+        // The second alert is still opened.
+        for element in ["header", "main", "aside"] {
+            assert_eq!(page[element], (Some(String::new()), Some(String::from("true"))));
+        }
+        restore(&mut stack, &mut page, "second");
+        assert_eq!(page, initial_page());
+
+        // Three alerts, the one in the middle closing first.
+        make_inert(&mut stack, &mut page, "bottom", &["header"]);
+        make_inert(&mut stack, &mut page, "middle", &["header", "main"]);
+        make_inert(&mut stack, &mut page, "top", &["main", "aside"]);
+        restore(&mut stack, &mut page, "middle");
+        assert_eq!(page["header"], (Some(String::new()), Some(String::from("true"))));
+        assert_eq!(page["main"], (Some(String::new()), Some(String::from("true"))));
+        restore(&mut stack, &mut page, "bottom");
+        assert_eq!(page["header"], initial_page()["header"]);
+        restore(&mut stack, &mut page, "top");
+        assert_eq!(page, initial_page());
+    }
+
+    #[test]
+    fn test_child_closes_parent() {
+        // The example of `Swal::push`: the child closes its parent from its "then" callback.
+        let (parent_id, parent) = register_alert(|r| log_event(format!("parent {}", r)));
+        let (child_id, child) = register_alert(|r| {
+            log_event(format!("child {}", r));
+            assert!(Swal::close(Some(SwalResult::denied())));
+        });
+        registry::with_instance(&child_id, |i| i.parent = Some(parent_id.clone()));
+        assert!(Swal::close_id(&child_id, Some(SwalResult::confirmed().into_any())));
+        assert!(child.is_closed());
+        assert!(parent.is_closed());
+        assert!(!registry::is_active(&parent_id));
+        assert_eq!(EVENTS.with(|e| e.take()), ["child confirmed", "parent denied"]);
+
+        // The root of the parent isn't one of the elements made inert by the child,
+        // so the child doesn't bring it back while it's closing.
+        let mut stack = InertStack::new();
+        let mut page = initial_page();
+        page.insert("parent", (None, Some(String::from("false"))));
+        make_inert(&mut stack, &mut page, "parent", &["header", "main", "aside"]);
+        make_inert(&mut stack, &mut page, "child", &["header", "main", "aside"]);
+        page.insert("parent", (None, Some(String::from("true"))));
+        restore(&mut stack, &mut page, "parent");
+        restore(&mut stack, &mut page, "child");
+        assert_eq!(page.remove("parent"), Some((None, Some(String::from("true")))));
+        assert_eq!(page, initial_page());
+    }
 }
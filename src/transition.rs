@@ -0,0 +1,160 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use leptos::leptos_dom::helpers::TimeoutHandle;
+use leptos::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::js_sys::{Array, Function, Object, Promise, Reflect};
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+use web_sys::{window, Element};
+
+/// A small delay added to the fallback timeout, so that it
+/// doesn't win the race against the end of the animations.
+const FALLBACK_MARGIN: Duration = Duration::from_millis(50);
+
+/// Calls `f` once the transitions of `swal` and the animations
+/// of its popup (the `.swal-container` element) are over.
+///
+/// The end of the animations is detected via `Element.getAnimations()`,
+/// whose "finished" promises don't require any closure to be kept alive.
+/// In case this API isn't available, or if the promises never settle,
+/// a timeout based on the computed styles calls `f` anyway.
+///
+/// `f` is called exactly once, and it's called right away if nothing
+/// is animated (for example when `animation` is `SwalAnimation::Off`).
+pub(crate) fn when_animations_end(swal: &Element, f: impl FnOnce() + 'static) {
+    // The stylesheets don't animate anything in this case
+    // (see the "animation" option).
+    if swal.class_list().contains("swal-no-animation") {
+        f();
+        return;
+    }
+
+    let popup = swal.query_selector(".swal-container").ok().flatten();
+
+    let mut animations = get_animations(swal);
+    let mut longest = get_longest_time(swal, "transition");
+    if let Some(popup) = &popup {
+        animations.extend(get_animations(popup));
+        longest = longest.max(get_longest_time(popup, "animation"));
+    }
+
+    if animations.is_empty() && longest.is_zero() {
+        f();
+        return;
+    }
+
+    let callback = Rc::new(RefCell::new(Some(f)));
+    let fallback_handle = Rc::new(RefCell::new(None::<TimeoutHandle>));
+
+    let fallback_callback = callback.clone();
+    if let Ok(handle) = set_timeout_with_handle(
+        move || {
+            if let Some(f) = fallback_callback.take() {
+                f();
+            }
+        },
+        longest + FALLBACK_MARGIN,
+    ) {
+        *fallback_handle.borrow_mut() = Some(handle);
+    }
+
+    if !animations.is_empty() {
+        let finished = animations
+            .iter()
+            .filter_map(|animation| Reflect::get(animation, &JsValue::from_str("finished")).ok())
+            .collect::<Array>();
+        // "finished" is rejected when an animation gets canceled,
+        // which also means it's over.
+        let all = Promise::all_settled(&finished);
+        spawn_local(async move {
+            let _ = JsFuture::from(all).await;
+            if let Some(f) = callback.take() {
+                if let Some(handle) = fallback_handle.take() {
+                    handle.clear();
+                }
+                f();
+            }
+        });
+    }
+}
+
+/// Gets the running animations and transitions of the element (not its children).
+/// It returns an empty list if `Element.getAnimations()` isn't supported.
+fn get_animations(el: &Element) -> Vec<JsValue> {
+    let Some(get_animations) = Reflect::get(el, &JsValue::from_str("getAnimations"))
+        .ok()
+        .and_then(|f| f.dyn_into::<Function>().ok())
+    else {
+        return Vec::new();
+    };
+    // Calling getAnimations() flushes the pending style changes,
+    // so the transitions that have just been triggered are included.
+    get_animations
+        .call1(el, &Object::new())
+        .ok()
+        .and_then(|list| list.dyn_into::<Array>().ok())
+        .map(|list| list.iter().collect())
+        .unwrap_or_default()
+}
+
+/// Gets the time it takes for all the transitions (`property` is "transition")
+/// or all the animations (`property` is "animation") of an element to end,
+/// according to its computed styles.
+fn get_longest_time(el: &Element, property: &str) -> Duration {
+    let Some(style) = window().and_then(|w| w.get_computed_style(el).ok().flatten()) else {
+        return Duration::ZERO;
+    };
+    let durations = style
+        .get_property_value(&format!("{}-duration", property))
+        .unwrap_or_default();
+    let delays = style
+        .get_property_value(&format!("{}-delay", property))
+        .unwrap_or_default();
+    if property == "animation" {
+        let names = style.get_property_value("animation-name").unwrap_or_default();
+        if names.trim().is_empty() || names.trim() == "none" {
+            return Duration::ZERO;
+        }
+    }
+    longest_time(&parse_css_times(&durations), &parse_css_times(&delays))
+}
+
+/// Parses a list of CSS times, such as `"0.2s, 150ms"`, into seconds.
+/// Invalid values are considered to be `0`.
+pub(crate) fn parse_css_times(value: &str) -> Vec<f64> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            if let Some(ms) = v.strip_suffix("ms") {
+                ms.trim().parse::<f64>().map(|ms| ms / 1000.0)
+            } else if let Some(s) = v.strip_suffix('s') {
+                s.trim().parse::<f64>()
+            } else {
+                v.parse::<f64>()
+            }
+            .unwrap_or(0.0)
+        })
+        .collect()
+}
+
+/// Gets the longest "duration + delay" among the given lists of times (in seconds).
+/// As in CSS, if there are fewer delays than durations, then the delays are repeated.
+pub(crate) fn longest_time(durations: &[f64], delays: &[f64]) -> Duration {
+    let longest = durations
+        .iter()
+        .enumerate()
+        .map(|(i, duration)| {
+            let delay = if delays.is_empty() {
+                0.0
+            } else {
+                delays[i % delays.len()]
+            };
+            duration.max(0.0) + delay
+        })
+        .fold(0.0_f64, f64::max);
+    Duration::from_secs_f64(longest.max(0.0))
+}